@@ -0,0 +1,79 @@
+//! Compares spawning many identical spawnables one at a time against [`SpawnCommands::spawn_batch_with`].
+//!
+//! Both sides queue their spawns as commands and then run the spawn system once, so children are invoked
+//! a single time for all entities in either case.
+//!
+//! Run with `cargo bench --bench spawn_batch`.
+
+#![allow(deprecated)]
+
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use moonshine_spawn::prelude::*;
+
+const COUNT: usize = 10_000;
+const RUNS: u32 = 10;
+
+#[derive(Component, Clone)]
+#[allow(dead_code)]
+struct Position(f32, f32);
+
+#[derive(Component, Clone)]
+#[allow(dead_code)]
+struct Velocity(f32, f32);
+
+#[derive(Component, Clone)]
+#[allow(dead_code)]
+struct Health(u32);
+
+#[derive(Clone)]
+struct Particle;
+
+impl Spawn for Particle {
+    type Output = (Position, Velocity, Health, Name);
+
+    fn spawn(&self, _: &World, _: Entity) -> Self::Output {
+        (
+            Position(0.0, 0.0),
+            Velocity(1.0, 1.0),
+            Health(100),
+            Name::new("Particle"),
+        )
+    }
+}
+
+fn world() -> World {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, SpawnPlugin));
+    std::mem::take(app.world_mut())
+}
+
+fn measure(f: impl Fn(&mut World)) -> Duration {
+    let mut total = Duration::ZERO;
+    for _ in 0..RUNS {
+        let mut world = world();
+        let start = Instant::now();
+        f(&mut world);
+        total += start.elapsed();
+    }
+    total / RUNS
+}
+
+fn main() {
+    let naive = measure(|world| {
+        let mut commands = world.commands();
+        for _ in 0..COUNT {
+            commands.spawn_with(Particle);
+        }
+        world.flush();
+        world.run_schedule(First);
+    });
+    let batched = measure(|world| {
+        world.commands().spawn_batch_with(COUNT, Particle);
+        world.flush();
+        world.run_schedule(First);
+    });
+    println!("spawn_with x {COUNT}: {naive:?}");
+    println!("spawn_batch_with({COUNT}): {batched:?}");
+}
@@ -0,0 +1,99 @@
+//! Counts allocations made while spawning children, with and without reusing the spawn system's worklist buffer.
+//!
+//! Each run spawns 10 roots with 2 levels of children. To measure the previous behavior, the buffer
+//! resource is removed before each run, so that it is allocated again.
+//!
+//! Run with `cargo bench --bench spawn_children_alloc`.
+
+#![allow(deprecated)]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use bevy::ecs::component::ComponentId;
+use bevy::prelude::*;
+use moonshine_spawn::{force_spawn_children, prelude::*};
+
+const ROOTS: usize = 10;
+const RUNS: usize = 1_000;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[derive(Component)]
+struct Chicken;
+
+fn world() -> World {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, SpawnPlugin));
+    std::mem::take(app.world_mut())
+}
+
+fn add_pending(world: &mut World) {
+    for _ in 0..ROOTS {
+        world.spawn(spawn_children(|chicken| {
+            chicken.spawn(Chicken.with_children(|chicken| {
+                chicken.spawn(Chicken);
+            }));
+        }));
+    }
+}
+
+/// Returns the [`ComponentId`] of the private worklist buffer resource, once it exists.
+fn buffer_id(world: &World) -> Option<ComponentId> {
+    world
+        .components()
+        .iter()
+        .find(|info| info.name().ends_with("SpawnBuffer"))
+        .map(|info| info.id())
+}
+
+/// Returns the average number of allocations made while spawning children.
+fn measure(reuse_buffer: bool) -> f64 {
+    let mut world = world();
+    let mut schedule = Schedule::default();
+    schedule.add_systems(force_spawn_children());
+    // Warm up, so that schedule initialization is not counted:
+    add_pending(&mut world);
+    schedule.run(&mut world);
+    let id = buffer_id(&world).expect("spawn buffer resource should exist");
+
+    let mut total = 0;
+    for _ in 0..RUNS {
+        if !reuse_buffer {
+            world.remove_resource_by_id(id);
+        }
+        add_pending(&mut world);
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        schedule.run(&mut world);
+        total += ALLOCATIONS.load(Ordering::Relaxed) - before;
+    }
+    total as f64 / RUNS as f64
+}
+
+fn main() {
+    let fresh = measure(false);
+    let reused = measure(true);
+    println!("allocations per run with a fresh buffer: {fresh:.1}");
+    println!("allocations per run with a persistent buffer: {reused:.1}");
+}
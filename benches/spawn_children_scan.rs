@@ -0,0 +1,82 @@
+//! Compares finding pending [`SpawnChildren`] by scanning every entity against the query used by the spawn system.
+//!
+//! The world contains 100k entities, of which only 10 have pending children.
+//!
+//! Run with `cargo bench --bench spawn_children_scan`.
+
+#![allow(deprecated)]
+
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use moonshine_spawn::{force_spawn_children, prelude::*};
+
+const ENTITIES: usize = 100_000;
+const PENDING: usize = 10;
+const RUNS: u32 = 100;
+
+#[derive(Component)]
+struct Chicken;
+
+fn world() -> World {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, SpawnPlugin));
+    let mut world = std::mem::take(app.world_mut());
+    world.spawn_batch((0..ENTITIES).map(|_| Chicken));
+    world
+}
+
+fn add_pending(world: &mut World) {
+    for _ in 0..PENDING {
+        world.spawn(spawn_children(|chicken| {
+            chicken.spawn(Chicken);
+        }));
+    }
+}
+
+fn measure(mut f: impl FnMut(&mut World)) -> Duration {
+    let mut world = world();
+    let mut total = Duration::ZERO;
+    for _ in 0..RUNS {
+        add_pending(&mut world);
+        let start = Instant::now();
+        f(&mut world);
+        total += start.elapsed();
+        // Pending children are removed, so the next run starts from the same state:
+        let mut query = world.query_filtered::<Entity, With<SpawnChildren>>();
+        let pending: Vec<_> = query.iter(&world).collect();
+        for entity in pending {
+            world.entity_mut(entity).remove::<SpawnChildren>();
+        }
+    }
+    total / RUNS
+}
+
+fn main() {
+    // The previous implementation, which scanned every entity in the world:
+    let scan = measure(|world| {
+        let pending: Vec<_> = world
+            .iter_entities()
+            .filter(|entity| entity.contains::<SpawnChildren>())
+            .map(|entity| entity.id())
+            .collect();
+        assert_eq!(pending.len(), PENDING);
+    });
+
+    // The current implementation, which uses a query:
+    let mut query = None;
+    let query_only = measure(|world| {
+        let query =
+            query.get_or_insert_with(|| world.query_filtered::<Entity, With<SpawnChildren>>());
+        let pending: Vec<_> = query.iter(world).collect();
+        assert_eq!(pending.len(), PENDING);
+    });
+
+    let mut schedule = Schedule::default();
+    schedule.add_systems(force_spawn_children());
+    let invoke = measure(|world| schedule.run(world));
+
+    println!("find pending by scan ({ENTITIES} entities): {scan:?}");
+    println!("find pending by query ({ENTITIES} entities): {query_only:?}");
+    println!("force_spawn_children ({ENTITIES} entities, {PENDING} pending): {invoke:?}");
+}
@@ -0,0 +1,100 @@
+//! Derive macros for [`moonshine-spawn`](https://crates.io/crates/moonshine-spawn).
+//!
+//! See `moonshine_spawn::Spawn` for details.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Fields, Index};
+
+/// Derives `Spawn` for a struct by cloning each of its fields into a bundle.
+///
+/// Every field is treated as a component, and must implement `Component + Clone`.
+/// Fields annotated with `#[children]` are instead spawned as children of the entity.
+/// These fields must be iterable by reference, with items that implement `Spawn + Clone`.
+#[proc_macro_derive(Spawn, attributes(children))]
+pub fn derive_spawn(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            input.span(),
+            "`Spawn` may only be derived for structs",
+        ));
+    };
+
+    let mut component_types = Vec::new();
+    let mut components = Vec::new();
+    let mut children = Vec::new();
+
+    let fields = match &data.fields {
+        Fields::Named(fields) => fields.named.iter().collect(),
+        Fields::Unnamed(fields) => fields.unnamed.iter().collect(),
+        Fields::Unit => Vec::new(),
+    };
+
+    for (index, field) in fields.into_iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => quote!(#ident),
+            None => {
+                let index = Index::from(index);
+                quote!(#index)
+            }
+        };
+        if field
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("children"))
+        {
+            children.push(member);
+        } else {
+            let ty = &field.ty;
+            component_types.push(quote!(#ty));
+            components.push(quote!(::core::clone::Clone::clone(&self.#member)));
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // Bundles are nested in pairs to avoid the tuple size limit:
+    let (mut output_type, mut output) = if children.is_empty() {
+        (quote!(()), quote!(()))
+    } else {
+        (
+            quote!(::moonshine_spawn::SpawnChildren),
+            quote! {
+                ::moonshine_spawn::spawn_children(|builder| {
+                    #(
+                        for child in &self.#children {
+                            builder.spawn_with(::core::clone::Clone::clone(child));
+                        }
+                    )*
+                })
+            },
+        )
+    };
+    for (ty, component) in component_types.iter().zip(components).rev() {
+        output_type = quote!((#ty, #output_type));
+        output = quote!((#component, #output));
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::moonshine_spawn::Spawn for #ident #ty_generics #where_clause {
+            type Output = #output_type;
+
+            fn spawn(
+                &self,
+                _world: &::moonshine_spawn::__private::World,
+                _entity: ::moonshine_spawn::__private::Entity,
+            ) -> Self::Output {
+                #output
+            }
+        }
+    })
+}
@@ -0,0 +1,972 @@
+//! Support for spawnables defined as assets in RON files.
+//!
+//! This module is only available with the `asset` feature.
+//!
+//! # Usage
+//! A spawnable asset describes a list of reflected components and a list of children.
+//! Any component used in a spawnable asset must be registered with the [`AppTypeRegistry`]
+//! and reflect [`Component`]:
+//!
+//! ```ron
+//! (
+//!     components: {
+//!         "my_game::Chicken": (),
+//!         "bevy_core::name::Name": "Chicken",
+//!     },
+//!     children: [
+//!         (
+//!             components: {
+//!                 "my_game::ChickenHead": (),
+//!             },
+//!         ),
+//!     ],
+//! )
+//! ```
+//!
+//! Add the [`SpawnAssetPlugin`] to your app, and load any files with the `.spawn.ron` extension
+//! using the [`AssetServer`]. Once loaded, each asset is registered with [`Spawnables`] under a [`SpawnKey`]
+//! derived from its path, without any extensions. For example, `prefabs/chicken.spawn.ron` is registered as
+//! `prefabs/chicken` (see [`SpawnKey::from_path`]).
+//!
+//! Note that the [`Handle`] returned from the [`AssetServer`] does not need to be kept alive,
+//! as [`Spawnables`] keeps its own handle to each registered asset.
+//!
+//! # Waiting for Assets
+//! This module also provides [`SpawnWhenLoaded`], which may be used to defer any spawnable until
+//! a given asset is loaded. This requires the [`SpawnAssetPlugin`].
+//!
+//! To track the progress of such spawns across frames, such as on a loading screen, use a [`SpawnTask`].
+
+use std::any::TypeId;
+use std::fmt::{self, Display, Formatter};
+use std::sync::Arc;
+use std::task::Poll;
+
+use bevy_app::prelude::*;
+use bevy_asset::{io::Reader, prelude::*, AssetLoader, LoadContext, UntypedAssetId};
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::{Children, Parent};
+use bevy_reflect::{
+    serde::TypedReflectDeserializer, PartialReflect, TypePath, TypeRegistry, TypeRegistryArc,
+};
+use bevy_utils::tracing::{error, warn};
+use serde::de::{
+    DeserializeSeed, Deserializer, Error as DeserializeError, IgnoredAny, MapAccess, SeqAccess,
+    Visitor,
+};
+
+use crate::{
+    finish_spawned, insert_reflect, invoke_spawn_children_of, spawned_descendants, validate_key,
+    PendingSpawnKey, Sealed, SpawnChildren, SpawnKey, SpawnKind, SpawnOnce, SpawnSource, Spawnable,
+    SpawnableOnce, Spawnables, SpawnedChild, SpawnedEntity,
+};
+
+/// A [`Plugin`] which loads spawnable assets and registers them with [`Spawnables`].
+///
+/// This plugin requires the `AssetPlugin` and the [`SpawnPlugin`](crate::SpawnPlugin).
+pub struct SpawnAssetPlugin;
+
+impl Plugin for SpawnAssetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<SpawnableAsset>()
+            .init_asset_loader::<SpawnableAssetLoader>()
+            .add_systems(
+                PreUpdate,
+                (
+                    register_spawnable_assets,
+                    spawn_when_loaded.run_if(any_with_component::<Loading>),
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// An [`Asset`] which describes a spawnable using reflected components.
+///
+/// See [module documentation](self) for details.
+#[derive(Asset, TypePath, Default)]
+pub struct SpawnableAsset {
+    components: Vec<Box<dyn PartialReflect>>,
+    children: Vec<SpawnableAsset>,
+}
+
+impl SpawnableAsset {
+    /// Parses a [`SpawnableAsset`] from RON text using the given [`TypeRegistry`].
+    pub fn from_ron(text: &str, registry: &TypeRegistry) -> Result<Self, SpawnableAssetError> {
+        let mut deserializer = ron::de::Deserializer::from_str(text)?;
+        let asset = SpawnableAssetDeserializer { registry }
+            .deserialize(&mut deserializer)
+            .map_err(|error| deserializer.span_error(error))?;
+        deserializer
+            .end()
+            .map_err(|error| deserializer.span_error(error))?;
+        Ok(asset)
+    }
+
+    /// Creates a [`SpawnableAsset`] from the reflected components of the given [`Entity`] and its children.
+    ///
+    /// Any component which does not reflect [`Component`] is skipped with a warning.
+    /// The [`Parent`] and [`Children`] components are not captured, as the hierarchy is captured recursively instead.
+    /// Components inserted by this crate to track spawned entities, such as [`SpawnSource`], [`SpawnKind`] and
+    /// [`SpawnedEntity`], are not captured either, since they are inserted again when the snapshot is spawned.
+    pub fn from_entity(world: &World, entity: Entity, registry: &TypeRegistry) -> Self {
+        let entity_ref = world.entity(entity);
+        let skipped = [
+            TypeId::of::<Parent>(),
+            TypeId::of::<Children>(),
+            TypeId::of::<SpawnSource>(),
+            TypeId::of::<SpawnKind>(),
+            TypeId::of::<SpawnedEntity>(),
+            TypeId::of::<SpawnedChild>(),
+        ];
+        let components = entity_ref
+            .archetype()
+            .components()
+            .filter_map(|id| world.components().get_info(id))
+            .filter(|info| {
+                info.type_id()
+                    .is_some_and(|type_id| !skipped.contains(&type_id))
+            })
+            .filter_map(|info| {
+                let reflect_component = info
+                    .type_id()
+                    .and_then(|type_id| registry.get(type_id))
+                    .and_then(|registration| registration.data::<ReflectComponent>());
+                let Some(reflect_component) = reflect_component else {
+                    warn!(
+                        "component of {entity:?} is not registered and is skipped: {}",
+                        info.name()
+                    );
+                    return None;
+                };
+                reflect_component
+                    .reflect(entity_ref)
+                    .map(|component| component.clone_value())
+            })
+            .collect();
+        let children = world
+            .get::<Children>(entity)
+            .into_iter()
+            .flatten()
+            .map(|&child| Self::from_entity(world, child, registry))
+            .collect();
+        Self {
+            components,
+            children,
+        }
+    }
+
+    /// Returns an iterator over the reflected components of this spawnable.
+    pub fn components(&self) -> impl Iterator<Item = &dyn PartialReflect> {
+        self.components.iter().map(|component| &**component)
+    }
+
+    /// Returns an iterator over the children of this spawnable.
+    pub fn children(&self) -> impl Iterator<Item = &SpawnableAsset> {
+        self.children.iter()
+    }
+}
+
+impl Spawnables {
+    /// Registers a [`SpawnableAsset`] with a unique [`SpawnKey`] and returns it.
+    ///
+    /// Assets loaded by the [`SpawnAssetPlugin`] are registered automatically.
+    /// If the asset is still loading when the key is spawned, nothing is inserted and an error is logged.
+    ///
+    /// # Warning
+    /// This function will panic if the given key is already registered or invalid (see [`SpawnKey::is_valid`]).
+    pub fn register_asset(
+        &mut self,
+        key: impl Into<SpawnKey>,
+        handle: Handle<SpawnableAsset>,
+    ) -> SpawnKey {
+        let key = key.into();
+        validate_key(&key).unwrap_or_else(|error| panic!("{error}"));
+        let spawnable = SpawnableAssetNode::new(SpawnableAssetSource::Handle(handle));
+        let previous = self.insert(key.clone(), Arc::new(spawnable));
+        assert!(previous.is_none(), "spawn key must be unique: {key:?}",);
+        key
+    }
+
+    /// Registers a snapshot of the given [`Entity`] and its children with a unique [`SpawnKey`] and returns it.
+    ///
+    /// Spawning the key later reproduces the captured components and children.
+    /// See [`SpawnableAsset::from_entity`] for details.
+    ///
+    /// Note that [`Spawnables`] must be removed from the [`World`] to capture an entity from it:
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use moonshine_spawn::prelude::*;
+    ///
+    /// #[derive(Component, Reflect)]
+    /// #[reflect(Component)]
+    /// struct Chicken;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, SpawnPlugin)).register_type::<Chicken>();
+    /// let world = app.world_mut();
+    /// let chicken = world.spawn(Chicken).id();
+    /// world.resource_scope(|world, mut spawnables: Mut<Spawnables>| {
+    ///     spawnables.register_from_entity("chicken", world, chicken);
+    /// });
+    /// let copy = world.spawn_key("chicken").id();
+    /// assert!(world.entity(copy).contains::<Chicken>());
+    /// ```
+    ///
+    /// # Warning
+    /// This function will panic if the given key is already registered or invalid (see [`SpawnKey::is_valid`]).
+    pub fn register_from_entity(
+        &mut self,
+        key: impl Into<SpawnKey>,
+        world: &World,
+        entity: Entity,
+    ) -> SpawnKey {
+        let key = key.into();
+        validate_key(&key).unwrap_or_else(|error| panic!("{error}"));
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let snapshot = SpawnableAsset::from_entity(world, entity, &registry);
+        let spawnable = SpawnableAssetNode::new(SpawnableAssetSource::Snapshot(Arc::new(snapshot)));
+        let previous = self.insert(key.clone(), Arc::new(spawnable));
+        assert!(previous.is_none(), "spawn key must be unique: {key:?}",);
+        key
+    }
+}
+
+/// An error which may occur while loading a [`SpawnableAsset`].
+#[derive(Debug)]
+pub enum SpawnableAssetError {
+    /// The asset could not be read.
+    Io(std::io::Error),
+    /// The asset could not be parsed.
+    Ron(ron::error::SpannedError),
+}
+
+impl Display for SpawnableAssetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "failed to read spawnable asset: {error}"),
+            Self::Ron(error) => write!(f, "failed to parse spawnable asset: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for SpawnableAssetError {}
+
+impl From<std::io::Error> for SpawnableAssetError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<ron::error::SpannedError> for SpawnableAssetError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        Self::Ron(error)
+    }
+}
+
+/// An [`AssetLoader`] for [`SpawnableAsset`] in RON format.
+pub struct SpawnableAssetLoader {
+    registry: TypeRegistryArc,
+}
+
+impl FromWorld for SpawnableAssetLoader {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            registry: world.resource::<AppTypeRegistry>().0.clone(),
+        }
+    }
+}
+
+impl AssetLoader for SpawnableAssetLoader {
+    type Asset = SpawnableAsset;
+    type Settings = ();
+    type Error = SpawnableAssetError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let text = std::str::from_utf8(&bytes)
+            .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+        SpawnableAsset::from_ron(text, &self.registry.read())
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["spawn.ron"]
+    }
+}
+
+#[derive(Clone)]
+enum SpawnableAssetSource {
+    Handle(Handle<SpawnableAsset>),
+    Snapshot(Arc<SpawnableAsset>),
+}
+
+/// Spawns a [`SpawnableAsset`], or one of its descendants given by its path of child indices.
+///
+/// Children are spawned via [`SpawnChildren`], the same as children of any other spawnable.
+#[derive(Clone)]
+struct SpawnableAssetNode {
+    source: SpawnableAssetSource,
+    path: Vec<usize>,
+}
+
+impl SpawnableAssetNode {
+    fn new(source: SpawnableAssetSource) -> Self {
+        Self {
+            source,
+            path: Vec::new(),
+        }
+    }
+
+    /// Inserts the components and children of this node into the given entity, and returns `true` if successful.
+    fn insert(&self, root: &SpawnableAsset, world: &mut World, entity: Entity) -> bool {
+        let Some(asset) = self
+            .path
+            .iter()
+            .try_fold(root, |asset, &index| asset.children.get(index))
+        else {
+            error!(
+                "spawnable asset has changed while spawning: {:?}",
+                self.path
+            );
+            return false;
+        };
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let mut entity_mut = world.entity_mut(entity);
+        insert_reflect(&mut entity_mut, asset.components(), &registry.read());
+        if !asset.children.is_empty() {
+            let mut children = SpawnChildren::with_capacity(asset.children.len());
+            for index in 0..asset.children.len() {
+                let mut path = self.path.clone();
+                path.push(index);
+                children.add_child(Self {
+                    source: self.source.clone(),
+                    path,
+                });
+            }
+            entity_mut.insert(children);
+        }
+        true
+    }
+}
+
+impl Sealed for SpawnableAssetNode {}
+
+impl Spawnable for SpawnableAssetNode {
+    fn spawn(&self, world: &mut World, entity: Entity) {
+        let spawned = match &self.source {
+            SpawnableAssetSource::Handle(handle) => {
+                world.resource_scope(|world, assets: Mut<Assets<SpawnableAsset>>| {
+                    let Some(asset) = assets.get(handle) else {
+                        error!("spawnable asset is not loaded: {handle:?}");
+                        return false;
+                    };
+                    self.insert(asset, world, entity)
+                })
+            }
+            SpawnableAssetSource::Snapshot(asset) => self.insert(asset, world, entity),
+        };
+        if spawned {
+            finish_spawned(world, entity);
+        }
+    }
+}
+
+impl SpawnableOnce for SpawnableAssetNode {
+    fn spawn_once(self, world: &mut World, entity: Entity) {
+        Spawnable::spawn(&self, world, entity);
+    }
+
+    fn spawn_once_dyn(self: Box<Self>, world: &mut World, entity: Entity) {
+        SpawnableOnce::spawn_once(*self, world, entity);
+    }
+}
+
+/// A [`SpawnOnce`] which defers another spawnable until the given asset is loaded.
+///
+/// When spawned, the entity only receives a [`Loading`] marker. Once the asset is loaded, the marker is removed
+/// and the given spawnable is spawned into the same entity, along with any of its children.
+///
+/// This requires the [`SpawnAssetPlugin`].
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::{prelude::*, asset::SpawnWhenLoaded};
+///
+/// #[derive(Component)]
+/// struct Tree;
+///
+/// fn spawn_tree(mut commands: Commands, server: Res<AssetServer>) {
+///     let image: Handle<Image> = server.load("tree.png");
+///     commands.spawn_once_with(SpawnWhenLoaded::new(image.clone(), (Tree, Sprite::from_image(image))));
+/// }
+/// ```
+pub struct SpawnWhenLoaded<A: Asset> {
+    handle: Handle<A>,
+    spawnable: Box<dyn SpawnableOnce>,
+}
+
+impl<A: Asset> SpawnWhenLoaded<A> {
+    /// Creates a new [`SpawnWhenLoaded`] which spawns the given spawnable once the given asset is loaded.
+    pub fn new(handle: Handle<A>, spawnable: impl SpawnOnce) -> Self {
+        Self {
+            handle,
+            spawnable: Box::new(spawnable),
+        }
+    }
+}
+
+impl<A: Asset> SpawnOnce for SpawnWhenLoaded<A> {
+    type Output = Loading;
+
+    fn spawn_once(self, _world: &World, _entity: Entity) -> Self::Output {
+        Loading {
+            handle: self.handle.untyped(),
+            spawnable: Some(self.spawnable),
+            is_loaded: |world, id| {
+                world
+                    .get_resource::<Assets<A>>()
+                    .is_some_and(|assets| assets.contains(id.typed::<A>()))
+            },
+        }
+    }
+}
+
+/// A [`Component`] which marks an [`Entity`] spawned with [`SpawnWhenLoaded`] while its asset is not loaded.
+#[derive(Component)]
+pub struct Loading {
+    handle: UntypedHandle,
+    spawnable: Option<Box<dyn SpawnableOnce>>,
+    is_loaded: fn(&World, UntypedAssetId) -> bool,
+}
+
+impl Loading {
+    /// Returns the handle of the asset this entity is waiting for.
+    pub fn handle(&self) -> &UntypedHandle {
+        &self.handle
+    }
+}
+
+/// A handle used to poll the progress of a spawn which depends on assets across multiple frames.
+///
+/// A task is complete once its [`Entity`] and all of its descendants are spawned, including any spawned
+/// with [`SpawnWhenLoaded`] or via a [`SpawnKey`] which is not registered yet.
+///
+/// A task is tied to a specific [`Entity`]. If the entity is despawned, the task is complete, but it yields no entity.
+///
+/// # Example
+/// ```
+/// # use std::task::Poll;
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::{prelude::*, asset::{SpawnTask, SpawnWhenLoaded}};
+///
+/// #[derive(Resource)]
+/// struct Level(SpawnTask);
+///
+/// #[derive(Component)]
+/// struct Tree;
+///
+/// fn spawn_level(mut commands: Commands, server: Res<AssetServer>) {
+///     let image: Handle<Image> = server.load("tree.png");
+///     let entity = commands
+///         .spawn_once_with(SpawnWhenLoaded::new(image.clone(), (Tree, Sprite::from_image(image))))
+///         .id();
+///     commands.insert_resource(Level(SpawnTask::new(entity)));
+/// }
+///
+/// fn update_loading_screen(world: &World, level: Res<Level>) {
+///     match level.0.poll(world) {
+///         Poll::Pending => info!("loading ({} remaining)", level.0.pending(world)),
+///         Poll::Ready(_) => info!("loaded"),
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpawnTask(Entity);
+
+impl SpawnTask {
+    /// Creates a new [`SpawnTask`] which tracks the spawn of the given [`Entity`].
+    pub fn new(entity: Entity) -> Self {
+        Self(entity)
+    }
+
+    /// Returns the [`Entity`] tracked by this task.
+    pub fn entity(&self) -> Entity {
+        self.0
+    }
+
+    /// Returns the number of entities in the hierarchy of this task which are not fully spawned yet.
+    pub fn pending(&self, world: &World) -> usize {
+        if world.get_entity(self.0).is_err() {
+            return 0;
+        }
+        std::iter::once(self.0)
+            .chain(spawned_descendants(world, self.0))
+            .filter(|&entity| {
+                let entity = world.entity(entity);
+                entity.contains::<Loading>()
+                    || entity.contains::<SpawnChildren>()
+                    || entity.contains::<PendingSpawnKey>()
+            })
+            .count()
+    }
+
+    /// Returns `true` if this task is complete.
+    pub fn is_complete(&self, world: &World) -> bool {
+        self.pending(world) == 0
+    }
+
+    /// Polls this task for completion.
+    ///
+    /// Returns `Poll::Ready(Some(entity))` once the entity is fully spawned,
+    /// or `Poll::Ready(None)` if the entity was despawned.
+    pub fn poll(&self, world: &World) -> Poll<Option<Entity>> {
+        if world.get_entity(self.0).is_err() {
+            Poll::Ready(None)
+        } else if self.is_complete(world) {
+            Poll::Ready(Some(self.0))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl From<Entity> for SpawnTask {
+    fn from(entity: Entity) -> Self {
+        Self::new(entity)
+    }
+}
+
+fn spawn_when_loaded(world: &mut World, query: &mut QueryState<(Entity, &Loading)>) {
+    let entities: Vec<_> = query
+        .iter(world)
+        .filter(|(_, loading)| (loading.is_loaded)(world, loading.handle.id()))
+        .map(|(entity, _)| entity)
+        .collect();
+    for entity in entities {
+        let Some(Loading {
+            spawnable: Some(spawnable),
+            ..
+        }) = world.entity_mut(entity).take::<Loading>()
+        else {
+            continue;
+        };
+        spawnable.spawn_once_dyn(world, entity);
+        invoke_spawn_children_of(world, |_, roots| roots.push(entity));
+    }
+}
+
+fn register_spawnable_assets(
+    mut events: EventReader<AssetEvent<SpawnableAsset>>,
+    server: Res<AssetServer>,
+    mut spawnables: ResMut<Spawnables>,
+) {
+    for event in events.read() {
+        let AssetEvent::Added { id } = event else {
+            continue;
+        };
+        let (Some(path), Some(handle)) = (server.get_path(*id), server.get_id_handle(*id)) else {
+            continue;
+        };
+        let key = SpawnKey::from_path(path.path());
+        if let Err(error) = validate_key(&key) {
+            error!("{error}");
+            continue;
+        }
+        if spawnables.contains(&key) {
+            error!("spawn key must be unique: {key:?}");
+            continue;
+        }
+        spawnables.register_asset(key, handle);
+    }
+}
+
+struct SpawnableAssetDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'de> DeserializeSeed<'de> for SpawnableAssetDeserializer<'_> {
+    type Value = SpawnableAsset;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_struct("SpawnableAsset", &["components", "children"], self)
+    }
+}
+
+impl<'de> Visitor<'de> for SpawnableAssetDeserializer<'_> {
+    type Value = SpawnableAsset;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a spawnable asset")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut asset = SpawnableAsset::default();
+        while let Some(field) = map.next_key_seed(FieldDeserializer)? {
+            match field {
+                "components" => {
+                    asset.components = map.next_value_seed(ComponentsDeserializer {
+                        registry: self.registry,
+                    })?;
+                }
+                "children" => {
+                    asset.children = map.next_value_seed(ChildrenDeserializer {
+                        registry: self.registry,
+                    })?;
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                    return Err(A::Error::unknown_field(field, &["components", "children"]));
+                }
+            }
+        }
+        Ok(asset)
+    }
+}
+
+struct FieldDeserializer;
+
+impl<'de> DeserializeSeed<'de> for FieldDeserializer {
+    type Value = &'static str;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl Visitor<'_> for FieldDeserializer {
+    type Value = &'static str;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a field identifier")
+    }
+
+    fn visit_str<E: DeserializeError>(self, value: &str) -> Result<Self::Value, E> {
+        match value {
+            "components" => Ok("components"),
+            "children" => Ok("children"),
+            _ => Err(E::unknown_field(value, &["components", "children"])),
+        }
+    }
+}
+
+struct ComponentsDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'de> DeserializeSeed<'de> for ComponentsDeserializer<'_> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ComponentsDeserializer<'_> {
+    type Value = Vec<Box<dyn PartialReflect>>;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a map of components by type path")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut components = Vec::new();
+        while let Some(type_path) = map.next_key::<String>()? {
+            let Some(registration) = self.registry.get_with_type_path(&type_path) else {
+                return Err(A::Error::custom(format!(
+                    "component is not registered: {type_path}"
+                )));
+            };
+            if registration.data::<ReflectComponent>().is_none() {
+                return Err(A::Error::custom(format!(
+                    "type does not reflect `Component`: {type_path}"
+                )));
+            }
+            components.push(
+                map.next_value_seed(TypedReflectDeserializer::new(registration, self.registry))?,
+            );
+        }
+        Ok(components)
+    }
+}
+
+struct ChildrenDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'de> DeserializeSeed<'de> for ChildrenDeserializer<'_> {
+    type Value = Vec<SpawnableAsset>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ChildrenDeserializer<'_> {
+    type Value = Vec<SpawnableAsset>;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a list of spawnable assets")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut children = Vec::new();
+        while let Some(child) = seq.next_element_seed(SpawnableAssetDeserializer {
+            registry: self.registry,
+        })? {
+            children.push(child);
+        }
+        Ok(children)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::prelude::*;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Foo;
+
+    #[derive(Component, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Bar(u32);
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), SpawnPlugin))
+            .add_plugins(SpawnAssetPlugin)
+            .register_type::<Foo>()
+            .register_type::<Bar>();
+        app
+    }
+
+    const FOO: &str = r#"(
+        components: {
+            "moonshine_spawn::asset::tests::Foo": (),
+        },
+        children: [
+            (
+                components: {
+                    "moonshine_spawn::asset::tests::Bar": (42),
+                },
+            ),
+        ],
+    )"#;
+
+    #[test]
+    fn spawn_asset() {
+        let mut app = app();
+        let asset = {
+            let registry = app.world().resource::<AppTypeRegistry>().read();
+            SpawnableAsset::from_ron(FOO, &registry).unwrap()
+        };
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<SpawnableAsset>>()
+            .add(asset);
+        let world = app.world_mut();
+        world
+            .resource_mut::<Spawnables>()
+            .register_asset("FOO", handle);
+        let entity = world.spawn_key("FOO").id();
+        assert!(world.entity(entity).contains::<Foo>());
+        let child = world.entity(entity).get::<Children>().unwrap()[0];
+        assert_eq!(world.get::<Bar>(child), Some(&Bar(42)));
+    }
+
+    #[test]
+    fn spawn_asset_unregistered_component() {
+        let app = app();
+        let registry = app.world().resource::<AppTypeRegistry>().read();
+        let result = SpawnableAsset::from_ron(
+            r#"(components: { "moonshine_spawn::asset::tests::Baz": () })"#,
+            &registry,
+        );
+        assert!(result.is_err());
+    }
+
+    #[derive(Asset, TypePath)]
+    struct Baz;
+
+    #[test]
+    fn spawn_when_loaded() {
+        let mut app = app();
+        app.init_asset::<Baz>();
+        let handle = app.world().resource::<Assets<Baz>>().reserve_handle();
+        let entity = app
+            .world_mut()
+            .spawn_once_with(SpawnWhenLoaded::new(
+                handle.clone(),
+                Foo.with_children(|foo| {
+                    foo.spawn(Bar(1));
+                }),
+            ))
+            .id();
+        app.update();
+        assert!(app.world().entity(entity).contains::<Loading>());
+        assert!(!app.world().entity(entity).contains::<Foo>());
+
+        app.world_mut()
+            .resource_mut::<Assets<Baz>>()
+            .insert(&handle, Baz);
+        app.update();
+        let world = app.world();
+        assert!(!world.entity(entity).contains::<Loading>());
+        assert!(world.entity(entity).contains::<Foo>());
+        assert_eq!(world.get::<Children>(entity).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn spawn_when_loaded_middleware() {
+        #[derive(Component)]
+        struct Count(u32);
+
+        let mut app = app();
+        app.init_asset::<Baz>();
+        app.add_spawn_middleware(|world: &mut World, entity: Entity| {
+            let mut entity = world.entity_mut(entity);
+            match entity.get_mut::<Count>() {
+                Some(mut count) => count.0 += 1,
+                None => {
+                    entity.insert(Count(1));
+                }
+            }
+        });
+        let handle = app.world().resource::<Assets<Baz>>().reserve_handle();
+        let entity = app
+            .world_mut()
+            .spawn_once_with(SpawnWhenLoaded::new(handle.clone(), Foo))
+            .id();
+        app.update();
+        assert!(!app.world().entity(entity).contains::<Count>());
+
+        app.world_mut()
+            .resource_mut::<Assets<Baz>>()
+            .insert(&handle, Baz);
+        app.update();
+        assert_eq!(app.world().get::<Count>(entity).unwrap().0, 1);
+    }
+
+    #[test]
+    fn spawn_task() {
+        let mut app = app();
+        app.init_asset::<Baz>();
+        let handle = app.world().resource::<Assets<Baz>>().reserve_handle();
+        let entity = app
+            .world_mut()
+            .spawn_once_with(Foo.with_children(|foo| {
+                foo.spawn(SpawnWhenLoaded::new(handle.clone(), Bar(1)));
+                foo.spawn(Bar(2));
+            }))
+            .id();
+        let task = SpawnTask::new(entity);
+        app.update();
+        assert_eq!(task.pending(app.world()), 1);
+        assert_eq!(task.poll(app.world()), Poll::Pending);
+
+        app.world_mut()
+            .resource_mut::<Assets<Baz>>()
+            .insert(&handle, Baz);
+        app.update();
+        assert_eq!(task.poll(app.world()), Poll::Ready(Some(entity)));
+
+        app.world_mut().despawn(entity);
+        assert_eq!(task.poll(app.world()), Poll::Ready(None));
+    }
+
+    #[test]
+    fn register_from_entity() {
+        #[derive(Component)]
+        struct Unregistered;
+
+        let mut app = app();
+        let world = app.world_mut();
+        let entity = world.spawn((Foo, Unregistered)).with_child(Bar(7)).id();
+        world.resource_scope(|world, mut spawnables: Mut<Spawnables>| {
+            spawnables.register_from_entity("FOO", world, entity);
+        });
+        let copy = world.spawn_key("FOO").id();
+        assert_ne!(copy, entity);
+        assert!(world.entity(copy).contains::<Foo>());
+        assert!(!world.entity(copy).contains::<Unregistered>());
+        assert_eq!(
+            world.get::<SpawnSource>(copy).unwrap().key(),
+            &SpawnKey::new("FOO").into()
+        );
+
+        // Bookkeeping of the captured entity is not captured:
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let snapshot = SpawnableAsset::from_entity(world, copy, &registry);
+        assert!(snapshot
+            .components()
+            .all(|component| !component.represents::<SpawnSource>()));
+        assert_eq!(snapshot.components().count(), 1);
+        let children = world.get::<Children>(copy).unwrap();
+        assert_eq!(children.len(), 1);
+        assert_eq!(world.get::<Bar>(children[0]), Some(&Bar(7)));
+    }
+
+    #[test]
+    fn spawn_asset_children() {
+        #[derive(Resource, Default)]
+        struct Spawned(Vec<Entity>);
+
+        #[derive(Component)]
+        struct Layer;
+
+        let mut app = app();
+        app.init_resource::<Spawned>();
+        app.add_spawn_middleware(|world: &mut World, entity: Entity| {
+            world.entity_mut(entity).insert(Layer);
+        });
+        let world = app.world_mut();
+        world.add_observer(
+            |trigger: Trigger<ChildSpawned>, mut spawned: ResMut<Spawned>| {
+                spawned.0.push(trigger.event().child);
+            },
+        );
+        let entity = world.spawn(Foo).with_child(Bar(7)).id();
+        world.resource_scope(|world, mut spawnables: Mut<Spawnables>| {
+            spawnables.register_from_entity("FOO", world, entity);
+        });
+        let copy = world.spawn_key("FOO").id();
+        let child = world.get::<Children>(copy).unwrap()[0];
+        assert_eq!(world.resource::<Spawned>().0, [child]);
+        assert!(world.entity(copy).contains::<Layer>());
+        assert!(world.entity(child).contains::<Layer>());
+    }
+
+    #[test]
+    #[should_panic]
+    fn register_from_entity_invalid_key() {
+        let mut app = app();
+        let world = app.world_mut();
+        let entity = world.spawn(Foo).id();
+        world.resource_scope(|world, mut spawnables: Mut<Spawnables>| {
+            spawnables.register_from_entity("", world, entity);
+        });
+    }
+
+    #[test]
+    #[should_panic]
+    fn register_asset_invalid_key() {
+        let mut spawnables = Spawnables::default();
+        spawnables.register_asset("", Handle::default());
+    }
+
+    #[test]
+    fn key_from_path() {
+        let key = SpawnKey::from_path(std::path::Path::new("prefabs/chicken.spawn.ron"));
+        assert_eq!(key, SpawnKey::new("prefabs/chicken"));
+    }
+}
@@ -1,549 +1,8045 @@
-#![deprecated(
-    since = "0.2.4",
-    note = "see documentation at https://github.com/Zeenobit/moonshine_spawn for details"
+#![cfg_attr(
+    not(feature = "allow-deprecated"),
+    deprecated(
+        since = "0.2.4",
+        note = "see documentation at https://github.com/Zeenobit/moonshine_spawn for details"
+    )
 )]
 #![doc = include_str!("../README.md")]
 
-use std::fmt::{Debug, Formatter, Result as FormatResult};
+use std::any::{type_name, Any, TypeId};
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter, Result as FormatResult};
 use std::hash::{Hash, Hasher};
-use std::sync::Arc;
+#[cfg(feature = "kind")]
+use std::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
 
 use bevy_app::prelude::*;
+#[cfg(feature = "name")]
+use bevy_core::Name;
+use bevy_ecs::component::ComponentId;
 use bevy_ecs::prelude::*;
-use bevy_ecs::schedule::SystemConfigs;
-use bevy_ecs::system::EntityCommands;
-use bevy_hierarchy::BuildChildren;
-use bevy_reflect::prelude::*;
-use bevy_utils::HashMap;
+use bevy_ecs::schedule::{InternedScheduleLabel, ScheduleLabel, SystemConfigs};
+use bevy_ecs::system::{EntityCommands, ReadOnlySystemParam, SystemParamItem, SystemState};
+use bevy_ecs::world::DeferredWorld;
+use bevy_hierarchy::{BuildChildren, Children, DespawnRecursiveExt, Parent};
+use bevy_reflect::{prelude::*, FromType, TypeRegistry};
+#[cfg(feature = "transform")]
+use bevy_transform::components::Transform;
+#[cfg(feature = "trace")]
+use bevy_utils::tracing::{debug_span, info_span};
+use bevy_utils::{
+    tracing::{debug, error, warn},
+    HashMap, HashSet,
+};
+
+// Allows derive macros to refer to this crate from within:
+extern crate self as moonshine_spawn;
+
+#[cfg(feature = "derive")]
+pub use moonshine_spawn_derive::Spawn;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use bevy_ecs::prelude::{Entity, World};
+}
+
+#[cfg(feature = "asset")]
+pub mod asset;
+
+#[cfg(feature = "scene")]
+pub mod scene;
 
 pub mod prelude {
     pub use super::{
-        spawn_children, AddSpawnable, Spawn, SpawnChildBuilder, SpawnChildren, SpawnCommands,
-        SpawnKey, SpawnOnce, SpawnPlugin, SpawnWorld, Spawnables, WithChildren,
+        find_child_by_spawn_name, keyed_descendants, spawn_children, spawn_children_rev,
+        spawned_descendants, AddSpawnable, ChildSpawned, ConfiguredSpawnPlugin, InternedSpawnKey,
+        ParametricSpawn, PendingSpawnKey, RequiredResource, Spawn, SpawnChildBuilder,
+        SpawnChildren, SpawnCommands, SpawnComposite, SpawnConfig, SpawnContext, SpawnDirect,
+        SpawnEntityCommands, SpawnEntityWorld, SpawnError, SpawnFailed, SpawnFinalizer, SpawnFn,
+        SpawnId, SpawnIdentities, SpawnKey, SpawnKeyRef, SpawnKind, SpawnName, SpawnOnce,
+        SpawnPlugin, SpawnQueue, SpawnRegisterError, SpawnSet, SpawnSlot, SpawnSource,
+        SpawnTemplate, SpawnUniqueCommands, SpawnWithContext, SpawnWithParam, SpawnWorld,
+        Spawnable, Spawnables, SpawnedByKey, SpawnedEntity, TrySpawnOnce, WithChildren,
+        WithContext, WithFinalizer,
     };
+
+    pub use crate::spawn_keys;
+
+    #[cfg(feature = "describe")]
+    pub use super::SpawnNode;
+
+    #[cfg(feature = "metrics")]
+    pub use super::SpawnMetrics;
+
+    #[cfg(feature = "journal")]
+    pub use super::{SpawnJournal, SpawnRecord};
+
+    #[cfg(feature = "kind")]
+    pub use super::SpawnInstance;
 }
 
 pub struct SpawnPlugin;
 
-impl Plugin for SpawnPlugin {
-    fn build(&self, app: &mut App) {
-        app.register_type::<SpawnKey>()
-            .insert_resource(Spawnables::default())
-            .add_systems(First, invoke_spawn_children.run_if(should_spawn_children));
+impl SpawnPlugin {
+    /// Returns a [`ConfiguredSpawnPlugin`] which spawns children in the given schedule instead of [`First`].
+    pub fn in_schedule(self, schedule: impl ScheduleLabel) -> ConfiguredSpawnPlugin {
+        ConfiguredSpawnPlugin::default().in_schedule(schedule)
     }
-}
 
-/// Represents a type which spawns an [`Entity`] exactly once.
-///
-/// # Usage
-/// The output of a spawn is a [`Bundle`] which is inserted into the given spawned [`Entity`].
-///
-/// By default, all bundles implement this trait.
-pub trait SpawnOnce: 'static + Send + Sync {
-    type Output: Bundle;
+    /// Returns a [`ConfiguredSpawnPlugin`] which only spawns children if the given condition is met.
+    pub fn run_if<M>(self, condition: impl Condition<M>) -> ConfiguredSpawnPlugin {
+        ConfiguredSpawnPlugin::default().run_if(condition)
+    }
 
-    fn spawn_once(self, world: &World, entity: Entity) -> Self::Output;
+    /// Returns a [`ConfiguredSpawnPlugin`] which spawns the given [`SpawnKey`] in place of any invalid key.
+    ///
+    /// See [`SpawnConfig::missing_key_fallback`] for details.
+    pub fn with_missing_key_fallback(self, key: impl Into<SpawnKey>) -> ConfiguredSpawnPlugin {
+        ConfiguredSpawnPlugin::default().with_missing_key_fallback(key)
+    }
 }
 
-impl<T: Bundle> SpawnOnce for T {
-    type Output = Self;
-
-    fn spawn_once(self, _: &World, _: Entity) -> Self::Output {
-        self
+impl Plugin for SpawnPlugin {
+    fn build(&self, app: &mut App) {
+        build_spawn_plugin(app);
+        app.add_systems(
+            First,
+            force_spawn_children().in_set(SpawnSet::SpawnChildren),
+        );
     }
 }
 
-/// Represents a type which spawns an [`Entity`].
+/// A [`SystemSet`] used to order systems relative to the spawn system, added by the [`SpawnPlugin`].
 ///
-/// # Usage
-/// The output of a spawn is a [`Bundle`] which is inserted into the given spawned [`Entity`].
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_spawn::prelude::*;
 ///
-/// By default, anything which implements [`SpawnOnce`] and [`Clone`] also implements this trait.
-/// This includes bundles which can be cloned.
-pub trait Spawn: 'static + Send + Sync {
-    type Output: Bundle;
+/// fn after_spawn() {
+///     // All pending children are spawned at this point.
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, SpawnPlugin))
+///     .add_systems(First, after_spawn.after(SpawnSet::SpawnChildren));
+/// ```
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpawnSet {
+    /// The set which spawns all pending [`SpawnChildren`].
+    ///
+    /// This set runs in the [`First`] schedule, unless configured otherwise (see [`ConfiguredSpawnPlugin`]).
+    SpawnChildren,
+}
 
-    fn spawn(&self, world: &World, entity: Entity) -> Self::Output;
+/// A [`SpawnPlugin`] with a custom schedule or run conditions for spawning children.
+///
+/// By default, children are spawned during the [`First`] schedule, whenever there are any [`SpawnChildren`] to invoke.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_spawn::prelude::*;
+///
+/// #[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
+/// enum GameState {
+///     #[default]
+///     Loading,
+///     Playing,
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugins(MinimalPlugins)
+///     .add_plugins(bevy::state::app::StatesPlugin)
+///     .init_state::<GameState>()
+///     .add_plugins(
+///         SpawnPlugin
+///             .in_schedule(PreUpdate)
+///             .run_if(in_state(GameState::Playing)),
+///     );
+/// ```
+pub struct ConfiguredSpawnPlugin {
+    schedule: InternedScheduleLabel,
+    systems: Mutex<Option<SystemConfigs>>,
+    missing_key_fallback: Option<SpawnKey>,
 }
 
-impl<T: SpawnOnce + Clone> Spawn for T {
-    type Output = T::Output;
+impl ConfiguredSpawnPlugin {
+    /// Spawns children in the given schedule instead of [`First`].
+    pub fn in_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = schedule.intern();
+        self
+    }
 
-    fn spawn(&self, world: &World, entity: Entity) -> Self::Output {
-        self.clone().spawn_once(world, entity)
+    /// Only spawns children if the given condition is met.
+    ///
+    /// This may be called multiple times, in which case all conditions must be met.
+    pub fn run_if<M>(self, condition: impl Condition<M>) -> Self {
+        {
+            let mut systems = self.systems.lock().unwrap();
+            *systems = systems.take().map(|systems| systems.run_if(condition));
+        }
+        self
+    }
+
+    /// Spawns the given [`SpawnKey`] in place of any invalid key.
+    ///
+    /// See [`SpawnConfig::missing_key_fallback`] for details.
+    pub fn with_missing_key_fallback(mut self, key: impl Into<SpawnKey>) -> Self {
+        self.missing_key_fallback = Some(key.into());
+        self
     }
 }
 
-/// Trait used to register a spawnable with an [`App`].
-///
-/// # Usage
-/// A spawnable is any thing which implements [`Spawn`]. A spawnable is registered by a unique [`SpawnKey`].
-/// This spawn key may then be used to spawn a new instance of the spawnable.
-pub trait AddSpawnable {
-    fn add_spawnable(self, key: impl Into<SpawnKey>, _: impl Spawn) -> SpawnKey;
+impl Default for ConfiguredSpawnPlugin {
+    fn default() -> Self {
+        Self {
+            schedule: First.intern(),
+            systems: Mutex::new(Some(force_spawn_children())),
+            missing_key_fallback: None,
+        }
+    }
 }
 
-impl AddSpawnable for &mut App {
-    fn add_spawnable(self, key: impl Into<SpawnKey>, spawnable: impl Spawn) -> SpawnKey {
-        self.world_mut()
-            .resource_mut::<Spawnables>()
-            .register(key, spawnable)
+impl Plugin for ConfiguredSpawnPlugin {
+    fn build(&self, app: &mut App) {
+        build_spawn_plugin(app);
+        let systems = self
+            .systems
+            .lock()
+            .unwrap()
+            .take()
+            .expect("plugin must only be built once");
+        app.add_systems(self.schedule, systems.in_set(SpawnSet::SpawnChildren));
+        if let Some(key) = &self.missing_key_fallback {
+            app.world_mut()
+                .resource_mut::<SpawnConfig>()
+                .missing_key_fallback = Some(key.clone());
+        }
     }
 }
 
-/// Trait used to spawn spawnables either directly or via a [`SpawnKey`] using [`Commands`].
-pub trait SpawnCommands {
-    fn spawn_with(&mut self, _: impl Spawn) -> EntityCommands<'_>;
+fn build_spawn_plugin(app: &mut App) {
+    app.register_type::<SpawnKey>()
+        .register_type::<SpawnSource>()
+        .register_type::<SpawnName>()
+        .register_type::<SpawnKind>()
+        .register_type::<SpawnedEntity>()
+        .insert_resource(Spawnables::default())
+        .init_resource::<SpawnConfig>()
+        .init_resource::<SpawnQueue>()
+        .add_event::<SpawnFailed>();
 
-    fn spawn_once_with(&mut self, _: impl SpawnOnce) -> EntityCommands<'_>;
+    #[cfg(feature = "journal")]
+    app.init_resource::<SpawnJournal>();
 
-    fn spawn_key(&mut self, key: impl Into<SpawnKey>) -> EntityCommands<'_>;
+    #[cfg(feature = "metrics")]
+    app.init_resource::<SpawnMetrics>().add_systems(
+        First,
+        reset_spawn_metrics
+            .before(spawn_children_system)
+            .run_if(|metrics: Res<SpawnMetrics>| metrics.reset_each_frame),
+    );
+}
 
-    fn spawn_key_with(
-        &mut self,
-        key: impl Into<SpawnKey>,
-        bundle: impl Bundle,
-    ) -> EntityCommands<'_>;
+/// A [`Resource`] which records spawns, such that they may be replayed later.
+///
+/// This resource is only available with the `journal` feature. A record is added for every [`Entity`] spawned via
+/// a [`SpawnKey`], and for every child spawned from [`SpawnChildren`], in the order they are spawned.
+/// Spawns without a key which are not children are not recorded.
+///
+/// Only the key is recorded. Any bundles or children given along with a key at the call site
+/// (i.e. [`SpawnWorld::spawn_key_with`] or [`SpawnWorld::spawn_key_with_children`]) are **not** captured,
+/// and must be saved separately.
+///
+/// This is useful for save and load systems, which may serialize these records and replay them using
+/// [`SpawnWorld::spawn_key_at`]. Children spawned from [`SpawnChildren`] are marked (see [`SpawnRecord::child`]),
+/// since they are spawned again when their root is replayed. Use [`SpawnJournal::roots`] to get only the records
+/// which should be replayed.
+///
+/// Records are kept until they are cleared or drained. To bound memory usage, see [`SpawnJournal::set_limit`].
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::prelude::*;
+///
+/// #[derive(Component, Clone)]
+/// struct Chicken;
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, SpawnPlugin));
+/// app.add_spawnable("chicken", Chicken);
+///
+/// let chicken = app.world_mut().spawn_key("chicken").id();
+/// let records = app.world_mut().resource_mut::<SpawnJournal>().drain();
+/// assert_eq!(records[0].entity, chicken);
+/// assert_eq!(records[0].key, Some(SpawnKey::new("chicken")));
+///
+/// // Replay:
+/// for record in records.iter().filter(|record| !record.child) {
+///     let entity = app.world_mut().spawn_empty().id();
+///     app.world_mut().spawn_key_at(entity, record.key.clone().unwrap());
+/// }
+/// ```
+#[cfg(feature = "journal")]
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SpawnJournal {
+    records: VecDeque<SpawnRecord>,
+    limit: Option<usize>,
+    /// The child currently spawned from [`SpawnChildren`], along with its key, if it has one.
+    child: Option<(Entity, Option<SpawnKey>)>,
 }
 
-impl SpawnCommands for Commands<'_, '_> {
-    fn spawn_with(&mut self, spawnable: impl Spawn) -> EntityCommands<'_> {
-        let entity = self.spawn_empty().id();
-        self.queue(move |world: &mut World| {
-            Spawnable::spawn(&spawnable, world, entity);
-        });
-        self.entity(entity)
+#[cfg(feature = "journal")]
+impl SpawnJournal {
+    /// Returns an iterator over all records, in the order they were spawned.
+    pub fn records(&self) -> impl Iterator<Item = &SpawnRecord> {
+        self.records.iter()
     }
 
-    fn spawn_once_with(&mut self, spawnable: impl SpawnOnce) -> EntityCommands<'_> {
-        let entity = self.spawn_empty().id();
-        self.queue(move |world: &mut World| {
-            SpawnableOnce::spawn_once(spawnable, world, entity);
-        });
-        self.entity(entity)
+    /// Returns an iterator over all records which are not children (see [`SpawnRecord::child`]).
+    ///
+    /// These are the records which should be replayed to spawn everything again.
+    pub fn roots(&self) -> impl Iterator<Item = &SpawnRecord> {
+        self.records.iter().filter(|record| !record.child)
     }
 
-    fn spawn_key(&mut self, key: impl Into<SpawnKey>) -> EntityCommands<'_> {
-        let key: SpawnKey = key.into();
-        let entity = self.spawn_empty().id();
-        self.queue(move |world: &mut World| {
-            key.spawn_once(world, entity);
-        });
-        self.entity(entity)
+    /// Returns the number of records.
+    pub fn len(&self) -> usize {
+        self.records.len()
     }
 
-    fn spawn_key_with(
-        &mut self,
-        key: impl Into<SpawnKey>,
-        bundle: impl Bundle,
-    ) -> EntityCommands<'_> {
-        let key = key.into();
-        let entity = self.spawn_empty().id();
-        self.queue(move |world: &mut World| {
-            SpawnKeyWith(key, bundle).spawn_once(world, entity);
-        });
-        self.entity(entity)
+    /// Returns `true` if there are no records.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
     }
-}
-
-/// Trait used to spawn spawnables either directly or via a [`SpawnKey`] using [`World`].
-pub trait SpawnWorld {
-    fn spawn_with(&mut self, _: impl Spawn) -> EntityWorldMut;
-
-    fn spawn_once_with(&mut self, _: impl SpawnOnce) -> EntityWorldMut;
-
-    fn spawn_key(&mut self, key: impl Into<SpawnKey>) -> EntityWorldMut;
-
-    fn spawn_key_with(&mut self, key: impl Into<SpawnKey>, bundle: impl Bundle) -> EntityWorldMut;
-}
 
-impl SpawnWorld for World {
-    fn spawn_with(&mut self, spawnable: impl Spawn) -> EntityWorldMut {
-        let entity = self.spawn_empty().id();
-        Spawnable::spawn(&spawnable, self, entity);
-        invoke_spawn_children(self);
-        self.entity_mut(entity)
+    /// Sets the maximum number of records to keep.
+    ///
+    /// Once the limit is reached, the oldest records are discarded. Set this to `None` to keep all records.
+    /// By default, there is no limit.
+    pub fn set_limit(&mut self, limit: Option<usize>) {
+        self.limit = limit;
+        if let Some(limit) = limit {
+            let excess = self.records.len().saturating_sub(limit);
+            self.records.drain(..excess);
+        }
     }
 
-    fn spawn_once_with(&mut self, spawnable: impl SpawnOnce) -> EntityWorldMut {
-        let entity = self.spawn_empty().id();
-        SpawnableOnce::spawn_once(spawnable, self, entity);
-        invoke_spawn_children(self);
-        self.entity_mut(entity)
+    /// Returns the maximum number of records to keep, if any.
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
     }
 
-    fn spawn_key(&mut self, key: impl Into<SpawnKey>) -> EntityWorldMut {
-        let key: SpawnKey = key.into();
-        let entity = self.spawn_empty().id();
-        key.spawn_once(self, entity);
-        invoke_spawn_children(self);
-        self.entity_mut(entity)
+    /// Removes all records.
+    pub fn clear(&mut self) {
+        self.records.clear();
     }
 
-    fn spawn_key_with(&mut self, key: impl Into<SpawnKey>, bundle: impl Bundle) -> EntityWorldMut {
-        let key = key.into();
-        let entity = self.spawn_empty().id();
-        SpawnKeyWith(key, bundle).spawn_once(self, entity);
-        invoke_spawn_children(self);
-        self.entity_mut(entity)
+    /// Removes and returns all records, in the order they were spawned.
+    pub fn drain(&mut self) -> Vec<SpawnRecord> {
+        self.records.drain(..).collect()
     }
-}
 
-/// A [`Resource`] which contains all registered spawnables.
-#[derive(Resource, Default)]
-pub struct Spawnables(HashMap<SpawnKey, Arc<dyn Spawnable>>);
+    fn push(&mut self, record: SpawnRecord) {
+        if self.limit == Some(0) {
+            return;
+        }
+        if self.limit.is_some_and(|limit| self.records.len() >= limit) {
+            self.records.pop_front();
+        }
+        self.records.push_back(record);
+    }
 
-impl Spawnables {
-    /// Registers a spawnable with a unique [`SpawnKey`] and returns it.
+    /// Records a spawn via a [`SpawnKey`].
     ///
-    /// # Warning
-    /// This function will panic if the given key is already registered.
-    pub fn register<T>(&mut self, key: impl Into<SpawnKey>, spawnable: T) -> SpawnKey
-    where
-        T: 'static + Spawn + Send + Sync,
-    {
-        let key = key.into();
-        let previous = self.0.insert(key.clone(), Arc::new(spawnable));
-        assert!(previous.is_none(), "spawn key must be unique: {key:?}",);
-        key
+    /// If the entity is a child spawned from [`SpawnChildren`], its key is recorded along with it instead.
+    fn record_key(world: &mut World, entity: Entity, key: &SpawnKeyRef) {
+        if !world.contains_resource::<SpawnJournal>() {
+            return;
+        }
+        // Records are replayable, so interned keys are recorded by name:
+        let Some(key) = key.resolve(world.resource::<Spawnables>()).cloned() else {
+            return;
+        };
+        let parent = world.get::<Parent>(entity).map(Parent::get);
+        let mut journal = world.resource_mut::<SpawnJournal>();
+        if let Some((child, child_key)) = &mut journal.child {
+            if *child == entity {
+                *child_key = Some(key);
+                return;
+            }
+        }
+        journal.push(SpawnRecord {
+            key: Some(key),
+            parent,
+            entity,
+            child: false,
+        });
     }
 
-    /// Returns an iterator over all registered [`SpawnKey`]s.
-    pub fn keys(&self) -> impl Iterator<Item = &SpawnKey> {
-        self.0.keys()
+    /// Marks the given child as being spawned from [`SpawnChildren`], and returns the previously marked child.
+    fn begin_child(world: &mut World, child: Entity) -> Option<(Entity, Option<SpawnKey>)> {
+        let mut journal = world.get_resource_mut::<SpawnJournal>()?;
+        journal.child.replace((child, None))
     }
 
-    fn fetch(&self, key: &SpawnKey) -> Option<Arc<dyn Spawnable>> {
-        self.0.get(key).cloned()
+    /// Records the given child once it is spawned, and restores the previously marked child.
+    fn end_child(world: &mut World, previous: Option<(Entity, Option<SpawnKey>)>) {
+        let Some(mut journal) = world.get_resource_mut::<SpawnJournal>() else {
+            return;
+        };
+        let Some((entity, key)) = std::mem::replace(&mut journal.child, previous) else {
+            return;
+        };
+        // The child may have been despawned if its spawn was cancelled (see `TrySpawnOnce`):
+        let Ok(entity_ref) = world.get_entity(entity) else {
+            return;
+        };
+        let parent = entity_ref.get::<Parent>().map(Parent::get);
+        world.resource_mut::<SpawnJournal>().push(SpawnRecord {
+            key,
+            parent,
+            entity,
+            child: true,
+        });
     }
 }
 
-/// A unique string-based identifier used to spawn a spawnable registered with [`Spawnables`].
-#[derive(Clone, Reflect)]
-pub struct SpawnKey(String);
+/// A single spawn, as recorded by the [`SpawnJournal`].
+#[cfg(feature = "journal")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpawnRecord {
+    /// The [`SpawnKey`] used to spawn the entity, if any.
+    pub key: Option<SpawnKey>,
+    /// The parent of the entity at the time it was spawned, if any.
+    pub parent: Option<Entity>,
+    /// The spawned [`Entity`].
+    pub entity: Entity,
+    /// If `true`, the entity was spawned as a child from [`SpawnChildren`].
+    ///
+    /// Such children are spawned again when their root is replayed, so they should not be replayed themselves.
+    pub child: bool,
+}
 
-impl SpawnKey {
-    pub fn new(name: impl Into<String>) -> Self {
-        Self(name.into())
-    }
+/// A [`Resource`] which tracks how many entities are spawned by the spawn system.
+///
+/// This resource is only available with the `metrics` feature, and is updated every time a root entity is spawned
+/// via [`SpawnCommands`], [`SpawnWorld`], or [`SpawnQueue`], and every time pending [`SpawnChildren`] are invoked,
+/// including via [`force_spawn_children`].
+#[cfg(feature = "metrics")]
+#[derive(Resource, Debug, Clone)]
+pub struct SpawnMetrics {
+    /// Number of entities spawned since the last reset.
+    ///
+    /// This includes all spawned children, and all root entities spawned via [`SpawnCommands`], [`SpawnWorld`],
+    /// or [`SpawnQueue`]. Each entity is counted once.
+    pub spawns_this_frame: usize,
+    /// Maximum depth of children reached since the last reset.
+    pub max_depth_reached: usize,
+    /// Number of batches processed since the last reset.
+    ///
+    /// Each level of children within a hierarchy is spawned in a separate batch.
+    pub iterations: usize,
+    /// If `true`, these metrics are reset at the start of every frame. By default, this is `true`.
+    pub reset_each_frame: bool,
+}
 
-    pub fn name(&self) -> &str {
-        &self.0
+#[cfg(feature = "metrics")]
+impl SpawnMetrics {
+    /// Resets all metrics to zero.
+    pub fn reset(&mut self) {
+        self.spawns_this_frame = 0;
+        self.max_depth_reached = 0;
+        self.iterations = 0;
     }
 }
 
-impl PartialEq for SpawnKey {
-    fn eq(&self, other: &Self) -> bool {
-        self.name() == other.name()
+#[cfg(feature = "metrics")]
+impl Default for SpawnMetrics {
+    fn default() -> Self {
+        Self {
+            spawns_this_frame: 0,
+            max_depth_reached: 0,
+            iterations: 0,
+            reset_each_frame: true,
+        }
     }
 }
 
-impl Eq for SpawnKey {}
+#[cfg(feature = "metrics")]
+fn reset_spawn_metrics(mut metrics: ResMut<SpawnMetrics>) {
+    metrics.reset();
+}
 
-impl Hash for SpawnKey {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.name().hash(state)
-    }
+/// A [`Resource`] used to configure the behavior of the spawn system.
+#[derive(Resource, Debug, Clone)]
+pub struct SpawnConfig {
+    /// Maximum depth of children which may be spawned from a single [`SpawnChildren`] hierarchy.
+    ///
+    /// Depth is tracked for each spawned child separately, relative to the entity which first requested
+    /// its children to be spawned. If a child would exceed this depth, an error is logged and its
+    /// [`SpawnChildren`] are discarded instead.
+    ///
+    /// This is useful to guard against spawnables which recursively spawn themselves.
+    /// By default, there is no limit.
+    pub max_child_depth: Option<usize>,
+    /// If `true`, a [`SpawnSource`] component is inserted into every entity spawned via a [`SpawnKey`].
+    ///
+    /// This may be disabled for performance-sensitive cases.
+    /// Note that [`SpawnWorld::respawn`] requires this component. By default, this is `true`.
+    pub record_spawn_source: bool,
+    /// If `true`, all [`SpawnKey`]s referenced by a [`SpawnChildren`] component are validated as soon as it is inserted.
+    ///
+    /// By default, a child with an invalid key only panics when it is spawned, which may be much later and
+    /// far from where the key was declared. When enabled, inserting a [`SpawnChildren`] component which
+    /// references an unregistered key panics immediately, with the parent entity and index of the child.
+    ///
+    /// This is useful during development. By default, this is `false`.
+    pub validate_child_keys: bool,
+    /// If `true`, spawning an [`Entity`] via a [`SpawnKey`] which is already used by one of its ancestors is reported as a cycle.
+    ///
+    /// A spawnable which spawns itself as a child, either directly or via other keys, would otherwise recurse forever.
+    /// When a cycle is detected, an error is logged with the chain of keys (i.e. `"a" -> "b" -> "a"`), and the entity
+    /// is not spawned. If it was spawned as a child from [`SpawnChildren`], it is also despawned and removed from its parent.
+    /// Only keys recorded by a [`SpawnSource`] are checked, so this requires [`SpawnConfig::record_spawn_source`].
+    ///
+    /// Note that cycles are only detected when spawned, by checking the ancestors of each spawned entity.
+    /// They are not detected when spawnables are registered, since the children of a spawnable are not known until
+    /// it is spawned.
+    ///
+    /// This complements [`SpawnConfig::max_child_depth`], which also guards against cycles through spawnables without keys.
+    /// This is useful during development. By default, this is `false`.
+    pub detect_key_cycles: bool,
+    /// If `true`, a [`Name`] component matching the [`SpawnKey`] is inserted into every entity spawned via a key.
+    ///
+    /// This makes spawned hierarchies easier to read in debugging tools.
+    /// If the spawnable already inserts a [`Name`], it is not overwritten. By default, this is `false`.
+    ///
+    /// This field is only available with the `name` feature.
+    #[cfg(feature = "name")]
+    pub name_from_key: bool,
+    /// If set, every root [`Entity`] spawned via [`SpawnCommands`], [`SpawnWorld`], or [`SpawnQueue`] is added as a child of this entity.
+    ///
+    /// This is useful to keep spawned content under a single root, such as for bulk despawning.
+    /// The entity is added to this parent before it is spawned, so a spawnable which sets its own parent overrides it.
+    /// Entities spawned with an explicit parent or into an existing entity (i.e. `spawn_key_child` or `spawn_key_at`)
+    /// are not affected. If the parent does not exist, it is ignored.
+    ///
+    /// Set this to `None` to clear it. By default, this is `None`.
+    pub default_parent: Option<Entity>,
+    /// If set, spawning via an invalid [`SpawnKey`] spawns this key instead, and logs an error rather than panicking.
+    ///
+    /// This is useful to keep the game running through bad data during development, by spawning a visible placeholder
+    /// in place of any missing spawnables. The placeholder entity still records the missing key as its [`SpawnSource`].
+    /// If the fallback key itself is not registered, spawning panics as usual.
+    ///
+    /// Note that [`SpawnWorld::try_spawn_key`], [`SpawnCommands::try_spawn_key`], and [`SpawnConfig::validate_child_keys`]
+    /// still report invalid keys.
+    /// By default, this is `None`.
+    pub missing_key_fallback: Option<SpawnKey>,
+    /// If `true`, a [`SpawnedEntity`] component is inserted into every root [`Entity`] spawned via [`SpawnCommands`], [`SpawnWorld`], or [`SpawnQueue`].
+    ///
+    /// Unlike [`SpawnSource`], this also marks entities spawned without a key (i.e. `spawn_with`).
+    /// Entities spawned via plain [`Commands::spawn`], or into an existing entity (i.e. `spawn_key_at`), are not marked.
+    /// By default, this is `false`.
+    pub mark_spawned_entities: bool,
 }
 
-impl Debug for SpawnKey {
-    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
-        f.debug_tuple("SpawnKey").field(&self.name()).finish()
+impl Default for SpawnConfig {
+    fn default() -> Self {
+        Self {
+            max_child_depth: None,
+            record_spawn_source: true,
+            validate_child_keys: false,
+            detect_key_cycles: false,
+            #[cfg(feature = "name")]
+            name_from_key: false,
+            default_parent: None,
+            missing_key_fallback: None,
+            mark_spawned_entities: false,
+        }
     }
 }
 
-impl From<String> for SpawnKey {
-    fn from(name: String) -> Self {
-        Self(name)
-    }
+/// Represents a type which spawns an [`Entity`] exactly once.
+///
+/// # Usage
+/// The output of a spawn is a [`Bundle`] which is inserted into the given spawned [`Entity`].
+///
+/// By default, all bundles implement this trait.
+pub trait SpawnOnce: 'static + Send + Sync {
+    type Output: Bundle;
+
+    fn spawn_once(self, world: &World, entity: Entity) -> Self::Output;
 }
 
-impl From<&str> for SpawnKey {
-    fn from(name: &str) -> Self {
-        Self(name.to_owned())
+impl<T: Bundle> SpawnOnce for T {
+    type Output = Self;
+
+    fn spawn_once(self, _: &World, _: Entity) -> Self::Output {
+        self
     }
 }
 
-/// Trait used to attach children to an [`Entity`] using a [`Bundle`].
+/// Represents a type which may spawn an [`Entity`] exactly once, or cancel the spawn.
 ///
-/// # Example
-/// ```
-/// # use bevy::prelude::*;
-/// # use moonshine_spawn::prelude::*;
+/// # Usage
+/// If the spawn succeeds, the output [`Bundle`] is inserted into the given spawned [`Entity`].
+/// Otherwise, if the spawn returns `None`, the spawned entity is despawned.
 ///
-/// #[derive(Component)]
-/// struct Foo;
+/// This may also be used to spawn children (see [`SpawnChildBuilder::try_spawn`]).
+/// In that case, the child is also removed from its parent, so no empty entity is left in the hierarchy.
 ///
-/// #[derive(Component)]
-/// struct Bar;
+/// By default, anything which implements [`SpawnOnce`] also implements this trait and always succeeds.
+pub trait TrySpawnOnce: 'static + Send + Sync {
+    type Output: Bundle;
+
+    fn try_spawn_once(self, world: &World, entity: Entity) -> Option<Self::Output>;
+}
+
+impl<T: SpawnOnce> TrySpawnOnce for T {
+    type Output = T::Output;
+
+    fn try_spawn_once(self, world: &World, entity: Entity) -> Option<Self::Output> {
+        Some(self.spawn_once(world, entity))
+    }
+}
+
+/// Represents a type which spawns an [`Entity`].
 ///
-/// let mut world = World::default();
-/// world.spawn(Foo.with_children(|foo| {
-///    foo.spawn(Bar);
-/// }));
+/// # Usage
+/// The output of a spawn is a [`Bundle`] which is inserted into the given spawned [`Entity`].
+///
+/// By default, anything which implements [`SpawnOnce`] and [`Clone`] also implements this trait.
+/// This includes bundles which can be cloned.
+///
+/// A spawnable only has immutable access to the [`World`]. To spawn other entities while spawning, use a [`SpawnQueue`].
+///
+/// With the `derive` feature, this trait may also be derived for structs. Each field is cloned into the
+/// output bundle as a component, except fields annotated with `#[children]`, which are spawned as children:
+///
+/// ```ignore
+/// #[derive(Spawn)]
+/// struct Chicken {
+///     name: Name,
+///     #[children]
+///     eggs: Vec<Egg>,
+/// }
 /// ```
-pub trait WithChildren: Bundle + Sized {
-    fn with_children(self, f: impl FnOnce(&mut SpawnChildBuilder)) -> (Self, SpawnChildren);
+pub trait Spawn: 'static + Send + Sync {
+    type Output: Bundle;
+
+    fn spawn(&self, world: &World, entity: Entity) -> Self::Output;
+
+    /// Returns the resources which this spawnable requires to spawn.
+    ///
+    /// This is only used to validate registered spawnables ahead of time (see [`Spawnables::validate`]).
+    /// By default, no resources are required.
+    fn required_resources(&self) -> Vec<RequiredResource> {
+        Vec::new()
+    }
+
+    /// Returns the components which this spawnable inserts into its entity, if known.
+    ///
+    /// This is only used to inspect registered spawnables without spawning them (see [`Spawnables::components_of`]).
+    /// By default, this is the list of components in [`Spawn::Output`], registered in the given [`World`] if needed.
+    /// Spawnables whose components depend on runtime data may return `None`.
+    fn output_components(&self, world: &mut World) -> Option<Vec<ComponentId>> {
+        Some(bundle_components::<Self::Output>(world))
+    }
 }
 
-impl<T: Bundle> WithChildren for T {
-    fn with_children(self, f: impl FnOnce(&mut SpawnChildBuilder)) -> (Self, SpawnChildren) {
-        let mut children = SpawnChildren::new();
-        let mut builder = SpawnChildBuilder(&mut children);
-        f(&mut builder);
-        (self, children)
+fn bundle_components<B: Bundle>(world: &mut World) -> Vec<ComponentId> {
+    world.register_bundle::<B>().explicit_components().to_vec()
+}
+
+/// A [`Resource`] required by a spawnable, as declared by [`Spawn::required_resources`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequiredResource {
+    type_id: TypeId,
+    name: &'static str,
+}
+
+impl RequiredResource {
+    /// Returns a [`RequiredResource`] for the given [`Resource`] type.
+    pub fn of<R: Resource>() -> Self {
+        Self {
+            type_id: TypeId::of::<R>(),
+            name: type_name::<R>(),
+        }
+    }
+
+    /// Returns the [`TypeId`] of the required resource.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Returns the type name of the required resource.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn exists(&self, world: &World) -> bool {
+        world
+            .components()
+            .get_resource_id(self.type_id)
+            .is_some_and(|id| world.contains_resource_by_id(id))
     }
 }
 
-/// A [`Component`] which stores a list of spawnables to spawn as children of its [`Entity`].
-#[derive(Component)]
-#[component(storage = "SparseSet")]
-pub struct SpawnChildren(Vec<Box<dyn SpawnableOnce>>);
+impl<T: SpawnOnce + Clone> Spawn for T {
+    type Output = T::Output;
 
-impl SpawnChildren {
-    fn new() -> Self {
-        Self(Vec::new())
+    fn spawn(&self, world: &World, entity: Entity) -> Self::Output {
+        self.clone().spawn_once(world, entity)
     }
+}
 
-    fn add_child(&mut self, spawnable: impl SpawnableOnce) {
-        self.0.push(Box::new(spawnable));
+/// Provides access to the [`World`] and the hierarchy of an [`Entity`] while it is being spawned.
+///
+/// # Usage
+/// The context is populated from the entity hierarchy at the time of spawn.
+/// When spawning children, each child is added to its parent before its spawnable is invoked.
+/// This means [`SpawnContext::parent`] returns the entity which owns the [`SpawnChildren`] that spawned it,
+/// and [`SpawnContext::root`] returns the top-most ancestor of the entity.
+///
+/// For entities which are not spawned as children, the parent is `None` and the root is the entity itself,
+/// unless the entity was manually parented before its spawnable was invoked.
+pub struct SpawnContext<'w> {
+    world: &'w World,
+    entity: Entity,
+}
+
+impl<'w> SpawnContext<'w> {
+    pub fn new(world: &'w World, entity: Entity) -> Self {
+        Self { world, entity }
     }
 
-    fn add_child_with_key(&mut self, key: SpawnKey) {
-        self.0.push(Box::new(key));
+    /// Returns the [`World`] in which the entity is being spawned.
+    pub fn world(&self) -> &'w World {
+        self.world
     }
 
-    fn invoke(world: &mut World, entity: Entity, mut child_spawned: impl FnMut(Entity)) {
-        if let Some(children) = world.entity_mut(entity).take::<SpawnChildren>() {
-            for spawnable in children.0 {
-                let child = world.spawn_empty().id();
-                spawnable.spawn_once_dyn(world, child);
-                child_spawned(child);
-                world.entity_mut(entity).add_child(child);
-            }
-        }
+    /// Returns the [`Entity`] being spawned.
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    /// Returns the parent of the [`Entity`] being spawned, if any.
+    pub fn parent(&self) -> Option<Entity> {
+        self.world.get::<Parent>(self.entity).map(Parent::get)
+    }
+
+    /// Returns an iterator over all ancestors of the [`Entity`] being spawned, starting with its parent.
+    pub fn ancestors(&self) -> impl Iterator<Item = Entity> + 'w {
+        let world = self.world;
+        std::iter::successors(self.parent(), move |&entity| {
+            world.get::<Parent>(entity).map(Parent::get)
+        })
+    }
+
+    /// Returns the top-most ancestor of the [`Entity`] being spawned, or the entity itself if it has no parent.
+    pub fn root(&self) -> Entity {
+        self.ancestors().last().unwrap_or(self.entity)
     }
 }
 
-/// An ergonomic function used to create a [`SpawnChildren`] component.
-#[must_use]
-pub fn spawn_children(f: impl FnOnce(&mut SpawnChildBuilder)) -> SpawnChildren {
-    let mut children = SpawnChildren::new();
-    f(&mut SpawnChildBuilder(&mut children));
-    children
+/// Represents a type which spawns an [`Entity`] with access to its [`SpawnContext`].
+///
+/// # Usage
+/// The output of a spawn is a [`Bundle`] which is inserted into the given spawned [`Entity`].
+///
+/// Use [`WithContext`] to use any type which implements this trait as a [`Spawn`].
+///
+/// By default, anything which implements [`Spawn`] also implements this trait.
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Owner(Entity);
+///
+/// struct Part;
+///
+/// impl SpawnWithContext for Part {
+///     type Output = Owner;
+///
+///     fn spawn_with_context(&self, context: &SpawnContext) -> Self::Output {
+///         Owner(context.root())
+///     }
+/// }
+///
+/// #[derive(Component)]
+/// struct Body;
+///
+/// let mut world = World::default();
+/// let body = world
+///     .spawn_once_with(Body.with_children(|body| {
+///         body.spawn_with(WithContext(Part));
+///     }))
+///     .id();
+/// ```
+pub trait SpawnWithContext: 'static + Send + Sync {
+    type Output: Bundle;
+
+    fn spawn_with_context(&self, context: &SpawnContext) -> Self::Output;
 }
 
-impl Default for SpawnChildren {
-    fn default() -> Self {
-        Self::new()
+impl<T: Spawn> SpawnWithContext for T {
+    type Output = T::Output;
+
+    fn spawn_with_context(&self, context: &SpawnContext) -> Self::Output {
+        self.spawn(context.world(), context.entity())
     }
 }
 
-pub struct SpawnChildBuilder<'a>(&'a mut SpawnChildren);
+/// An adapter which implements [`Spawn`] for any [`SpawnWithContext`].
+#[derive(Clone)]
+pub struct WithContext<T>(pub T);
 
-impl SpawnChildBuilder<'_> {
-    pub fn spawn(&mut self, spawnable: impl SpawnOnce) -> &mut Self {
-        self.0.add_child(spawnable);
-        self
+impl<T: SpawnWithContext> Spawn for WithContext<T> {
+    type Output = T::Output;
+
+    fn spawn(&self, world: &World, entity: Entity) -> Self::Output {
+        self.0.spawn_with_context(&SpawnContext::new(world, entity))
     }
+}
 
-    pub fn spawn_key(&mut self, key: impl Into<SpawnKey>) -> &mut Self {
-        self.0.add_child_with_key(key.into());
-        self
+/// An adapter which implements [`Spawn`] for any function of the form `Fn(&World, Entity) -> impl Bundle`.
+///
+/// See [`AddSpawnable::add_spawnable_with`] to register such functions directly.
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::prelude::*;
+///
+/// let mut world = World::default();
+/// let chicken = world
+///     .spawn_with(SpawnFn(|_: &World, _: Entity| Name::new("Chicken")))
+///     .id();
+/// assert_eq!(world.get::<Name>(chicken).unwrap().as_str(), "Chicken");
+/// ```
+#[derive(Clone)]
+pub struct SpawnFn<F>(pub F);
+
+impl<T, F> Spawn for SpawnFn<F>
+where
+    T: Bundle,
+    F: 'static + Send + Sync + Fn(&World, Entity) -> T,
+{
+    type Output = T;
+
+    fn spawn(&self, world: &World, entity: Entity) -> Self::Output {
+        (self.0)(world, entity)
     }
+}
 
-    pub fn spawn_key_with(&mut self, key: impl Into<SpawnKey>, bundle: impl Bundle) -> &mut Self {
-        self.0.add_child(SpawnKeyWith(key.into(), bundle));
-        self
+/// A type-erased [`Spawn`] which may be shared and spawned multiple times without a [`SpawnKey`].
+///
+/// Unlike [`SpawnFn`], the type of the function and its output [`Bundle`] are erased, so templates
+/// with different outputs may be stored together, such as in a [`Component`] or [`Resource`].
+/// Cloning a template is cheap, and all clones share the same function.
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Spawner(SpawnTemplate);
+///
+/// let name = "Chicken".to_string();
+/// let template = SpawnTemplate::new(move |_: &World, _| Name::new(name.clone()));
+///
+/// let mut world = World::default();
+/// world.spawn(Spawner(template.clone()));
+/// let chicken = world.spawn_with(template).id();
+/// assert_eq!(world.get::<Name>(chicken).unwrap().as_str(), "Chicken");
+/// ```
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+pub struct SpawnTemplate(Arc<dyn Fn(&World, Entity) -> SpawnComposite + Send + Sync>);
+
+impl SpawnTemplate {
+    /// Creates a new [`SpawnTemplate`] from the given function.
+    pub fn new<T: Bundle>(f: impl 'static + Send + Sync + Fn(&World, Entity) -> T) -> Self {
+        Self(Arc::new(move |world, entity| {
+            SpawnComposite::new().with(f(world, entity))
+        }))
     }
 }
 
-trait Spawnable: 'static + Send + Sync {
-    fn spawn(&self, world: &mut World, entity: Entity);
+impl Spawn for SpawnTemplate {
+    type Output = SpawnComposite;
+
+    fn spawn(&self, world: &World, entity: Entity) -> Self::Output {
+        (self.0)(world, entity)
+    }
+
+    fn output_components(&self, _: &mut World) -> Option<Vec<ComponentId>> {
+        None
+    }
 }
 
-impl<T: Spawn> Spawnable for T {
-    fn spawn(&self, world: &mut World, entity: Entity) {
-        let bundle = self.spawn(world, entity);
-        world.entity_mut(entity).insert(bundle);
+/// Represents a type which spawns an [`Entity`] using some read-only [`SystemParam`](bevy_ecs::system::SystemParam).
+///
+/// # Usage
+/// The output of a spawn is a [`Bundle`] which is inserted into the given spawned [`Entity`].
+///
+/// This is useful for spawnables which depend on resources or queries, as an alternative to accessing the [`World`] directly.
+/// The system param state is cached between spawns. These spawnables are registered with
+/// [`AddSpawnable::add_spawnable_with_param`] and spawned using a [`SpawnKey`].
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy::ecs::system::SystemParamItem;
+/// # use moonshine_spawn::prelude::*;
+///
+/// #[derive(Resource)]
+/// struct DefaultChickenName(String);
+///
+/// struct Chicken;
+///
+/// impl SpawnWithParam for Chicken {
+///     type Param = Res<'static, DefaultChickenName>;
+///     type Output = Name;
+///
+///     fn spawn_with_param(&self, name: SystemParamItem<Self::Param>, _: Entity) -> Self::Output {
+///         Name::new(name.0.clone())
+///     }
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, SpawnPlugin))
+///     .insert_resource(DefaultChickenName("Chicken".to_string()));
+/// app.add_spawnable_with_param("chicken", Chicken);
+///
+/// let chicken = app.world_mut().spawn_key("chicken").id();
+/// assert_eq!(app.world().get::<Name>(chicken).unwrap().as_str(), "Chicken");
+/// ```
+pub trait SpawnWithParam: 'static + Send + Sync {
+    type Param: ReadOnlySystemParam + 'static;
+
+    type Output: Bundle;
+
+    fn spawn_with_param(&self, param: SystemParamItem<Self::Param>, entity: Entity)
+        -> Self::Output;
+}
+
+/// Represents a type which spawns an [`Entity`] given some parameters of type `P`.
+///
+/// # Usage
+/// The output of a spawn is a [`Bundle`] which is inserted into the given spawned [`Entity`].
+///
+/// This is useful for spawnables which differ only by some runtime values, such as a projectile with a given velocity.
+/// Parametric spawnables are registered with [`AddSpawnable::add_parametric_spawnable`] and spawned
+/// using `spawn_key_with_params`.
+///
+/// By default, this trait is implemented for any function of the form `Fn(P, &World, Entity) -> impl Bundle`.
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Velocity(f32);
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, SpawnPlugin));
+/// app.add_parametric_spawnable("projectile", |speed: f32, _: &World, _: Entity| Velocity(speed));
+///
+/// let projectile = app.world_mut().spawn_key_with_params("projectile", 10.0_f32).id();
+/// assert_eq!(app.world().get::<Velocity>(projectile).unwrap().0, 10.0);
+/// ```
+pub trait ParametricSpawn<P>: 'static + Send + Sync {
+    type Output: Bundle;
+
+    fn spawn(&self, params: P, world: &World, entity: Entity) -> Self::Output;
+}
+
+impl<P, T, F> ParametricSpawn<P> for F
+where
+    T: Bundle,
+    F: 'static + Send + Sync + Fn(P, &World, Entity) -> T,
+{
+    type Output = T;
+
+    fn spawn(&self, params: P, world: &World, entity: Entity) -> Self::Output {
+        self(params, world, entity)
     }
 }
 
-trait SpawnableOnce: 'static + Send + Sync {
-    fn spawn_once(self, world: &mut World, entity: Entity);
+/// Trait used to register a spawnable with an [`App`].
+///
+/// # Usage
+/// A spawnable is any thing which implements [`Spawn`] (or [`SpawnDirect`]). A spawnable is registered by a unique [`SpawnKey`].
+/// This spawn key may then be used to spawn a new instance of the spawnable.
+pub trait AddSpawnable {
+    fn add_spawnable(self, key: impl Into<SpawnKey>, _: impl Spawnable) -> SpawnKey;
 
-    fn spawn_once_dyn(self: Box<Self>, world: &mut World, entity: Entity);
+    /// Registers a spawnable with a unique [`SpawnKey`], or returns an error if the key is already registered.
+    ///
+    /// See [`Spawnables::try_register`] for details.
+    fn try_add_spawnable(
+        self,
+        key: impl Into<SpawnKey>,
+        _: impl Spawnable,
+    ) -> Result<SpawnKey, SpawnRegisterError>;
+
+    /// Registers many spawnables, each with a unique [`SpawnKey`]. See [`Spawnables::register_many`].
+    fn add_spawnables<K: Into<SpawnKey>>(
+        self,
+        _: impl IntoIterator<Item = (K, Arc<dyn Spawnable>)>,
+    ) -> Vec<SpawnKey>;
+
+    /// Registers a function of the form `Fn(&World, Entity) -> impl Bundle` as a spawnable with a unique [`SpawnKey`].
+    ///
+    /// See [`SpawnFn`] for details.
+    fn add_spawnable_with<T: Bundle>(
+        self,
+        key: impl Into<SpawnKey>,
+        _: impl 'static + Send + Sync + Fn(&World, Entity) -> T,
+    ) -> SpawnKey;
+
+    /// Registers a [`ParametricSpawn`] with a unique [`SpawnKey`]. See [`Spawnables::register_parametric`].
+    fn add_parametric_spawnable<P: 'static + Send + Sync>(
+        self,
+        key: impl Into<SpawnKey>,
+        _: impl ParametricSpawn<P>,
+    ) -> SpawnKey;
+
+    /// Registers a [`SpawnWithParam`] with a unique [`SpawnKey`]. See [`Spawnables::register_with_param`].
+    fn add_spawnable_with_param(self, key: impl Into<SpawnKey>, _: impl SpawnWithParam)
+        -> SpawnKey;
+
+    /// Registers a function which is invoked for every spawned [`Entity`], after the output of its spawnable
+    /// is inserted, but before any of its [`SpawnChildren`] are spawned.
+    ///
+    /// This is useful for global post-processing of all spawned entities, such as adding a physics layer to everything.
+    /// Multiple functions are invoked in the order they were added.
+    ///
+    /// Middleware runs exactly once per spawned entity. Entities spawned with
+    /// `SpawnWhenLoaded` are skipped while loading and processed once their spawnable is spawned.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use moonshine_spawn::prelude::*;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Chicken;
+    ///
+    /// #[derive(Component)]
+    /// struct Layer(u32);
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, SpawnPlugin));
+    /// app.add_spawn_middleware(|world: &mut World, entity: Entity| {
+    ///     world.entity_mut(entity).insert(Layer(1));
+    /// });
+    ///
+    /// let chicken = app.world_mut().spawn_with(Chicken).id();
+    /// assert!(app.world().entity(chicken).contains::<Layer>());
+    /// ```
+    fn add_spawn_middleware(self, _: impl Fn(&mut World, Entity) + Send + Sync + 'static) -> Self;
 }
 
-impl<T: SpawnOnce> SpawnableOnce for T {
-    fn spawn_once(self, world: &mut World, entity: Entity) {
-        let bundle = self.spawn_once(world, entity);
-        world.entity_mut(entity).insert(bundle);
+impl AddSpawnable for &mut App {
+    fn add_spawnable(self, key: impl Into<SpawnKey>, spawnable: impl Spawnable) -> SpawnKey {
+        self.world_mut()
+            .resource_mut::<Spawnables>()
+            .register(key, spawnable)
+    }
+
+    fn try_add_spawnable(
+        self,
+        key: impl Into<SpawnKey>,
+        spawnable: impl Spawnable,
+    ) -> Result<SpawnKey, SpawnRegisterError> {
+        self.world_mut()
+            .resource_mut::<Spawnables>()
+            .try_register(key, spawnable)
+    }
+
+    fn add_spawnables<K: Into<SpawnKey>>(
+        self,
+        entries: impl IntoIterator<Item = (K, Arc<dyn Spawnable>)>,
+    ) -> Vec<SpawnKey> {
+        self.world_mut()
+            .resource_mut::<Spawnables>()
+            .register_many(entries)
+    }
+
+    fn add_spawnable_with<T: Bundle>(
+        self,
+        key: impl Into<SpawnKey>,
+        spawnable: impl 'static + Send + Sync + Fn(&World, Entity) -> T,
+    ) -> SpawnKey {
+        self.add_spawnable(key, SpawnFn(spawnable))
     }
 
-    fn spawn_once_dyn(self: Box<Self>, world: &mut World, entity: Entity) {
-        SpawnableOnce::spawn_once(*self, world, entity);
+    fn add_parametric_spawnable<P: 'static + Send + Sync>(
+        self,
+        key: impl Into<SpawnKey>,
+        spawnable: impl ParametricSpawn<P>,
+    ) -> SpawnKey {
+        self.world_mut()
+            .resource_mut::<Spawnables>()
+            .register_parametric(key, spawnable)
+    }
+
+    fn add_spawnable_with_param(
+        self,
+        key: impl Into<SpawnKey>,
+        spawnable: impl SpawnWithParam,
+    ) -> SpawnKey {
+        self.world_mut()
+            .resource_mut::<Spawnables>()
+            .register_with_param(key, spawnable)
+    }
+
+    fn add_spawn_middleware(self, f: impl Fn(&mut World, Entity) + Send + Sync + 'static) -> Self {
+        self.world_mut()
+            .get_resource_or_insert_with(SpawnMiddleware::default)
+            .push(Arc::new(f));
+        self
+    }
+}
+
+/// Trait used to spawn spawnables either directly or via a [`SpawnKey`] using [`Commands`].
+pub trait SpawnCommands {
+    fn spawn_with(&mut self, _: impl Spawn) -> EntityCommands<'_>;
+
+    fn spawn_once_with(&mut self, _: impl SpawnOnce) -> EntityCommands<'_>;
+
+    /// Spawns the given spawnable and all of its children within the same command.
+    ///
+    /// Unlike [`SpawnCommands::spawn_with`], which defers spawning children until the next spawn system,
+    /// the entire hierarchy of the spawned entity is spawned as soon as this command is applied.
+    /// Any [`SpawnChildren`] outside of this hierarchy are not affected.
+    ///
+    /// # Ordering
+    /// Commands are still deferred, so the hierarchy is not available when this function returns.
+    /// It is only available to commands queued after it, and to any systems which run after the commands are applied.
+    fn spawn_with_immediate(&mut self, _: impl Spawn) -> EntityCommands<'_>;
+
+    /// Spawns `count` instances of the given spawnable and returns their entities.
+    ///
+    /// All instances are spawned using a single command.
+    ///
+    /// # Ordering
+    /// The outputs of all instances are computed before any of them is inserted. Unlike spawning each instance
+    /// separately, a spawnable can not observe the output of earlier instances of the same batch.
+    fn spawn_batch_with(&mut self, count: usize, _: impl Spawn) -> Vec<Entity>;
+
+    /// Spawns a [`TrySpawnOnce`] spawnable.
+    ///
+    /// Commands are deferred, so the result cannot be returned directly.
+    /// Instead, if the spawn is cancelled, the spawned [`Entity`] is despawned.
+    fn spawn_once_try_with(&mut self, _: impl TrySpawnOnce) -> EntityCommands<'_>;
+
+    /// Spawns a spawnable via a [`SpawnKey`] or an [`InternedSpawnKey`].
+    ///
+    /// See [`SpawnWorld::spawn_key`] for details.
+    fn spawn_key(&mut self, key: impl Into<SpawnKeyRef>) -> EntityCommands<'_>;
+
+    /// Spawns a spawnable via a [`SpawnKey`] as a child of the given parent [`Entity`].
+    ///
+    /// See [`SpawnWorld::spawn_key_child`] for details.
+    fn spawn_key_child(&mut self, parent: Entity, key: impl Into<SpawnKey>) -> EntityCommands<'_>;
+
+    /// Spawns a spawnable via a [`SpawnKey`] into an [`Entity`] which was reserved but not spawned yet.
+    ///
+    /// See [`SpawnWorld::spawn_key_at`] for details.
+    fn spawn_key_at(&mut self, entity: Entity, key: impl Into<SpawnKey>) -> EntityCommands<'_>;
+
+    /// Spawns a spawnable via a [`SpawnKey`] without panicking if the key is not registered.
+    ///
+    /// Commands are deferred, so the error cannot be returned directly. Instead, if the key is invalid or requires
+    /// parameters, the spawned [`Entity`] is despawned and a [`SpawnFailed`] event is sent.
+    /// Otherwise, the key is spawned the same as [`SpawnWorld::try_spawn_key`].
+    fn try_spawn_key(&mut self, key: impl Into<SpawnKeyRef>) -> EntityCommands<'_>;
+
+    /// Spawns a spawnable via a [`SpawnKey`] and inserts the given [`Bundle`] into it.
+    ///
+    /// See [`SpawnWorld::spawn_key_with`] for details.
+    fn spawn_key_with(
+        &mut self,
+        key: impl Into<SpawnKey>,
+        bundle: impl Bundle,
+    ) -> EntityCommands<'_>;
+
+    /// Spawns a spawnable via a [`SpawnKey`] with the given [`Transform`].
+    ///
+    /// See [`SpawnWorld::spawn_key_at_transform`] for details.
+    #[cfg(feature = "transform")]
+    fn spawn_key_at_transform(
+        &mut self,
+        key: impl Into<SpawnKey>,
+        transform: Transform,
+    ) -> EntityCommands<'_> {
+        self.spawn_key_with(key, transform)
+    }
+
+    /// Spawns a spawnable via a [`SpawnKey`] with some additional children.
+    ///
+    /// The given children are spawned after any children of the registered spawnable.
+    fn spawn_key_with_children(
+        &mut self,
+        key: impl Into<SpawnKey>,
+        f: impl FnOnce(&mut SpawnChildBuilder),
+    ) -> EntityCommands<'_>;
+
+    /// Spawns a [`ParametricSpawn`] registered with the given [`SpawnKey`] using the given parameters.
+    fn spawn_key_with_params<P: 'static + Send + Sync>(
+        &mut self,
+        key: impl Into<SpawnKey>,
+        params: P,
+    ) -> EntityCommands<'_>;
+
+    /// Spawns a spawnable via a [`SpawnKey`] and returns a [`SpawnInstance`] of the given [`Component`] type.
+    ///
+    /// Commands are deferred, so the component cannot be checked immediately.
+    /// Instead, if the key is invalid or the spawned [`Entity`] does not have the component, the entity is despawned
+    /// and a [`SpawnFailed`] event is sent, the same as [`SpawnCommands::try_spawn_key`].
+    ///
+    /// This function is only available with the `kind` feature.
+    #[cfg(feature = "kind")]
+    fn spawn_key_as<T: Component>(&mut self, key: impl Into<SpawnKey>) -> SpawnInstance<T>;
+
+    /// Spawns a spawnable via a [`SpawnKey`] only if no [`Entity`] has been spawned with the given identity yet.
+    ///
+    /// Commands are deferred, so whether the identity is already spawned is only known once the command is applied.
+    /// Therefore, no entity is reserved. Instead, the returned [`SpawnUniqueCommands`] refers to either the existing
+    /// or the newly spawned [`Entity`], as resolved when the command is applied.
+    ///
+    /// See [`SpawnWorld::spawn_key_unique`] for details.
+    fn spawn_key_unique<I: Hash + Eq + Send + Sync + 'static>(
+        &mut self,
+        identity: I,
+        key: impl Into<SpawnKey>,
+    ) -> SpawnUniqueCommands<'_>;
+
+    /// Recursively despawns every [`Entity`] spawned via the given [`SpawnKey`].
+    ///
+    /// See [`SpawnWorld::despawn_all_with_key`] for details.
+    fn despawn_all_with_key(&mut self, key: impl Into<SpawnKey>);
+
+    /// Recursively despawns every [`Entity`] tagged with the given [`SpawnKind`].
+    ///
+    /// See [`SpawnWorld::despawn_all_of_kind`] for details.
+    fn despawn_all_of_kind(&mut self, kind: impl Into<SpawnKind>);
+}
+
+impl SpawnCommands for Commands<'_, '_> {
+    fn spawn_with(&mut self, spawnable: impl Spawn) -> EntityCommands<'_> {
+        let entity = spawn_root_deferred(self);
+        self.queue(move |world: &mut World| {
+            Spawnable::spawn(&spawnable, world, entity);
+        });
+        self.entity(entity)
+    }
+
+    fn spawn_once_with(&mut self, spawnable: impl SpawnOnce) -> EntityCommands<'_> {
+        let entity = spawn_root_deferred(self);
+        self.queue(move |world: &mut World| {
+            SpawnableOnce::spawn_once(spawnable, world, entity);
+        });
+        self.entity(entity)
+    }
+
+    fn spawn_with_immediate(&mut self, spawnable: impl Spawn) -> EntityCommands<'_> {
+        let entity = spawn_root_deferred(self);
+        self.queue(move |world: &mut World| {
+            Spawnable::spawn(&spawnable, world, entity);
+            invoke_spawn_children_of(world, |_, roots| roots.push(entity));
+        });
+        self.entity(entity)
+    }
+
+    fn spawn_batch_with(&mut self, count: usize, spawnable: impl Spawn) -> Vec<Entity> {
+        let entities: Vec<Entity> = (0..count).map(|_| spawn_root_deferred(self)).collect();
+        let batch = entities.clone();
+        self.queue(move |world: &mut World| {
+            spawn_batch_into(world, &batch, &spawnable);
+        });
+        entities
+    }
+
+    fn spawn_once_try_with(&mut self, spawnable: impl TrySpawnOnce) -> EntityCommands<'_> {
+        let entity = spawn_root_deferred(self);
+        self.queue(move |world: &mut World| {
+            try_spawn_once(spawnable, world, entity);
+        });
+        self.entity(entity)
+    }
+
+    fn spawn_key(&mut self, key: impl Into<SpawnKeyRef>) -> EntityCommands<'_> {
+        let key: SpawnKeyRef = key.into();
+        let entity = spawn_root_deferred(self);
+        self.queue(move |world: &mut World| {
+            key.spawn_once(world, entity);
+        });
+        self.entity(entity)
+    }
+
+    fn spawn_key_child(&mut self, parent: Entity, key: impl Into<SpawnKey>) -> EntityCommands<'_> {
+        let key: SpawnKey = key.into();
+        let child = self.spawn_empty().id();
+        self.entity(parent).add_child(child);
+        self.queue(move |world: &mut World| {
+            key.spawn_once(world, child);
+        });
+        self.entity(child)
+    }
+
+    fn spawn_key_at(&mut self, entity: Entity, key: impl Into<SpawnKey>) -> EntityCommands<'_> {
+        let key: SpawnKey = key.into();
+        self.queue(move |world: &mut World| {
+            key.spawn_once(world, entity);
+        });
+        self.entity(entity)
+    }
+
+    fn try_spawn_key(&mut self, key: impl Into<SpawnKeyRef>) -> EntityCommands<'_> {
+        let key: SpawnKeyRef = key.into();
+        let entity = spawn_root_deferred(self);
+        self.queue(move |world: &mut World| {
+            if let Err(error) = key.try_spawn_once(world, entity) {
+                error!("{error}");
+                world.despawn(entity);
+                world.send_event(SpawnFailed { entity, error });
+            }
+        });
+        self.entity(entity)
+    }
+
+    fn spawn_key_with(
+        &mut self,
+        key: impl Into<SpawnKey>,
+        bundle: impl Bundle,
+    ) -> EntityCommands<'_> {
+        let key = key.into();
+        let entity = spawn_root_deferred(self);
+        self.queue(move |world: &mut World| {
+            SpawnKeyWith(KeyedChild::new(key), bundle).spawn_once(world, entity);
+        });
+        self.entity(entity)
+    }
+
+    fn spawn_key_with_children(
+        &mut self,
+        key: impl Into<SpawnKey>,
+        f: impl FnOnce(&mut SpawnChildBuilder),
+    ) -> EntityCommands<'_> {
+        let spawnable = SpawnKeyWithChildren(KeyedChild::new(key), spawn_children(f));
+        let entity = spawn_root_deferred(self);
+        self.queue(move |world: &mut World| {
+            spawnable.spawn_once(world, entity);
+        });
+        self.entity(entity)
+    }
+
+    fn spawn_key_with_params<P: 'static + Send + Sync>(
+        &mut self,
+        key: impl Into<SpawnKey>,
+        params: P,
+    ) -> EntityCommands<'_> {
+        let key = key.into();
+        let entity = spawn_root_deferred(self);
+        self.queue(move |world: &mut World| {
+            SpawnKeyWithParams(KeyedChild::new(key), params).spawn_once(world, entity);
+        });
+        self.entity(entity)
+    }
+
+    #[cfg(feature = "kind")]
+    fn spawn_key_as<T: Component>(&mut self, key: impl Into<SpawnKey>) -> SpawnInstance<T> {
+        let key: SpawnKey = key.into();
+        let entity = self.try_spawn_key(key.clone()).id();
+        self.queue(move |world: &mut World| {
+            // Failed spawns are already despawned and reported:
+            if world.get_entity(entity).is_err() {
+                return;
+            }
+            if let Err(error) = check_instance::<T>(world, entity, &key) {
+                error!("{error}");
+                world.entity_mut(entity).despawn_recursive();
+                world.send_event(SpawnFailed { entity, error });
+            }
+        });
+        SpawnInstance::new(entity)
+    }
+
+    fn spawn_key_unique<I: Hash + Eq + Send + Sync + 'static>(
+        &mut self,
+        identity: I,
+        key: impl Into<SpawnKey>,
+    ) -> SpawnUniqueCommands<'_> {
+        let key: SpawnKey = key.into();
+        let slot = SpawnSlot::default();
+        let target = slot.clone();
+        self.queue(move |world: &mut World| {
+            let entity = world.spawn_key_unique(identity, key).id();
+            let _ = target.0.set(entity);
+        });
+        SpawnUniqueCommands {
+            commands: self.reborrow(),
+            slot,
+        }
+    }
+
+    fn despawn_all_with_key(&mut self, key: impl Into<SpawnKey>) {
+        let key = key.into();
+        self.queue(move |world: &mut World| {
+            world.despawn_all_with_key(key);
+        });
+    }
+
+    fn despawn_all_of_kind(&mut self, kind: impl Into<SpawnKind>) {
+        let kind = kind.into();
+        self.queue(move |world: &mut World| {
+            world.despawn_all_of_kind(kind);
+        });
+    }
+}
+
+/// Trait used to spawn spawnables either directly or via a [`SpawnKey`] using [`World`].
+pub trait SpawnWorld {
+    fn spawn_with(&mut self, _: impl Spawn) -> EntityWorldMut<'_>;
+
+    fn spawn_once_with(&mut self, _: impl SpawnOnce) -> EntityWorldMut<'_>;
+
+    /// Spawns `count` instances of the given spawnable and returns their entities.
+    ///
+    /// The output of the spawnable is computed for each entity first, and then inserted into all entities at once.
+    /// This is faster than spawning each instance separately, since every entity moves into the same archetype.
+    ///
+    /// # Ordering
+    /// Because all outputs are computed before any of them is inserted, a spawnable can not observe the output
+    /// of earlier instances of the same batch, unlike when spawning each instance separately.
+    fn spawn_batch_with(&mut self, count: usize, _: impl Spawn) -> Vec<Entity>;
+
+    /// Spawns a [`TrySpawnOnce`] spawnable.
+    ///
+    /// If the spawn is cancelled, the spawned [`Entity`] is despawned and `None` is returned.
+    fn spawn_once_try_with(&mut self, _: impl TrySpawnOnce) -> Option<EntityWorldMut<'_>>;
+
+    /// Spawns a spawnable via a [`SpawnKey`] or an [`InternedSpawnKey`].
+    ///
+    /// Interned keys are looked up by index rather than by name. See [`Spawnables::intern`] for details.
+    fn spawn_key(&mut self, key: impl Into<SpawnKeyRef>) -> EntityWorldMut<'_>;
+
+    /// Spawns a spawnable via a [`SpawnKey`] without spawning any of its [`SpawnChildren`].
+    ///
+    /// Unlike [`SpawnWorld::spawn_key`], which spawns all pending children before it returns, children of the
+    /// returned entity stay pending. This allows more children to be added (see [`SpawnEntityWorld::add_spawn_children`])
+    /// before they are all spawned together, either using [`SpawnEntityWorld::spawn_pending_children`] or by the
+    /// spawn system during the next [`First`] schedule.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use moonshine_spawn::prelude::*;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Chicken;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Hat;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, SpawnPlugin));
+    /// app.add_spawnable("chicken", Chicken);
+    ///
+    /// let chicken = app
+    ///     .world_mut()
+    ///     .spawn_key_deferred("chicken")
+    ///     .add_spawn_children(|chicken| {
+    ///         chicken.spawn(Hat);
+    ///     })
+    ///     .spawn_pending_children()
+    ///     .id();
+    /// let hat = app.world().get::<Children>(chicken).unwrap()[0];
+    /// assert!(app.world().entity(hat).contains::<Hat>());
+    /// ```
+    fn spawn_key_deferred(&mut self, key: impl Into<SpawnKey>) -> EntityWorldMut<'_>;
+
+    /// Spawns a spawnable via a [`SpawnKey`] and returns its [`Entity`], along with all of its direct children
+    /// spawned from [`SpawnChildren`], in the order they were spawned.
+    ///
+    /// This avoids querying the [`Children`] of the spawned entity afterwards.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use moonshine_spawn::prelude::*;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Chicken;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, SpawnPlugin));
+    /// app.add_spawnable_with("chicken", |_, _| Chicken.with_children(|chicken| {
+    ///     chicken.spawn(Name::new("Head"));
+    ///     chicken.spawn(Name::new("Tail"));
+    /// }));
+    ///
+    /// let (chicken, children) = app.world_mut().spawn_key_collect("chicken");
+    /// assert_eq!(children.len(), 2);
+    /// ```
+    fn spawn_key_collect(&mut self, key: impl Into<SpawnKey>) -> (Entity, Vec<Entity>);
+
+    /// Spawns a spawnable via a [`SpawnKey`] as a child of the given parent [`Entity`].
+    ///
+    /// The child is added to its parent before it is spawned, so its spawnable may access its parent
+    /// (see [`SpawnContext`]). Any [`SpawnChildren`] of the child are spawned as usual.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use moonshine_spawn::prelude::*;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Hat;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, SpawnPlugin));
+    /// app.add_spawnable("hat", Hat);
+    ///
+    /// let world = app.world_mut();
+    /// let chicken = world.spawn(Name::new("Chicken")).id();
+    /// let hat = world.spawn_key_child(chicken, "hat").id();
+    /// assert_eq!(world.get::<Parent>(hat).unwrap().get(), chicken);
+    /// ```
+    fn spawn_key_child(&mut self, parent: Entity, key: impl Into<SpawnKey>) -> EntityWorldMut<'_>;
+
+    /// Spawns a spawnable via a [`SpawnKey`] into an [`Entity`] which was reserved but not spawned yet.
+    ///
+    /// This is useful when entity IDs must be known in advance, such as for mapping entities between a client and server.
+    /// Only the given entity uses the reserved ID. Any children of the spawnable are spawned as new entities.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use moonshine_spawn::prelude::*;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Player;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, SpawnPlugin));
+    /// app.add_spawnable("player", Player);
+    ///
+    /// let world = app.world_mut();
+    /// let entity = world.entities().reserve_entity();
+    /// world.spawn_key_at(entity, "player");
+    /// assert!(world.entity(entity).contains::<Player>());
+    /// ```
+    ///
+    /// # Warning
+    /// This function will panic if the given entity was not reserved.
+    fn spawn_key_at(&mut self, entity: Entity, key: impl Into<SpawnKey>) -> EntityWorldMut<'_>;
+
+    /// Spawns a spawnable via a [`SpawnKey`] without panicking if the key is not registered.
+    ///
+    /// If the key is invalid, or requires parameters (see [`SpawnWorld::spawn_key_with_params`]),
+    /// no [`Entity`] is spawned and a [`SpawnError`] is returned instead.
+    fn try_spawn_key(
+        &mut self,
+        key: impl Into<SpawnKeyRef>,
+    ) -> Result<EntityWorldMut<'_>, SpawnError>;
+
+    /// Spawns a spawnable via a [`SpawnKey`] and inserts the given [`Bundle`] into it.
+    ///
+    /// The bundle is inserted after the spawnable, so any components in the bundle override
+    /// the components inserted by the spawnable. This is useful to customize a shared spawnable per instance.
+    ///
+    /// Note that a [`SpawnChildren`] in the bundle also replaces any children of the spawnable.
+    /// Use [`SpawnWorld::spawn_key_with_children`] to add more children instead.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use moonshine_spawn::prelude::*;
+    ///
+    /// #[derive(Component, Clone, PartialEq, Debug)]
+    /// enum Team {
+    ///     Red,
+    ///     Blue,
+    /// }
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, SpawnPlugin));
+    /// app.add_spawnable("soldier", (Name::new("Soldier"), Team::Red));
+    /// let soldier = app.world_mut().spawn_key_with("soldier", Team::Blue).id();
+    /// assert_eq!(app.world().get::<Team>(soldier), Some(&Team::Blue));
+    /// ```
+    fn spawn_key_with(
+        &mut self,
+        key: impl Into<SpawnKey>,
+        bundle: impl Bundle,
+    ) -> EntityWorldMut<'_>;
+
+    /// Spawns a spawnable via a [`SpawnKey`] with the given [`Transform`].
+    ///
+    /// This is useful to spawn a spawnable at some position, such as pickups or enemies.
+    /// The given transform replaces any [`Transform`] of the spawned root entity.
+    /// Local transforms of its children are preserved, so the entire hierarchy is moved along with its root.
+    ///
+    /// This function is only available with the `transform` feature.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use moonshine_spawn::prelude::*;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Coin;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, SpawnPlugin));
+    /// app.add_spawnable("coin", (Coin, Transform::default()));
+    ///
+    /// let transform = Transform::from_xyz(1.0, 2.0, 3.0);
+    /// let coin = app.world_mut().spawn_key_at_transform("coin", transform).id();
+    /// assert_eq!(app.world().get::<Transform>(coin), Some(&transform));
+    /// ```
+    #[cfg(feature = "transform")]
+    fn spawn_key_at_transform(
+        &mut self,
+        key: impl Into<SpawnKey>,
+        transform: Transform,
+    ) -> EntityWorldMut<'_> {
+        self.spawn_key_with(key, transform)
+    }
+
+    /// Spawns a spawnable via a [`SpawnKey`] with some additional children.
+    ///
+    /// The given children are spawned after any children of the registered spawnable.
+    fn spawn_key_with_children(
+        &mut self,
+        key: impl Into<SpawnKey>,
+        f: impl FnOnce(&mut SpawnChildBuilder),
+    ) -> EntityWorldMut<'_>;
+
+    /// Spawns a [`ParametricSpawn`] registered with the given [`SpawnKey`] using the given parameters.
+    fn spawn_key_with_params<P: 'static + Send + Sync>(
+        &mut self,
+        key: impl Into<SpawnKey>,
+        params: P,
+    ) -> EntityWorldMut<'_>;
+
+    /// Spawns a spawnable via a [`SpawnKey`] and returns a [`SpawnInstance`] of the given [`Component`] type.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use moonshine_spawn::prelude::*;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Enemy;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, SpawnPlugin));
+    /// app.add_spawnable("goblin", (Enemy, Name::new("Goblin")));
+    /// let goblin: SpawnInstance<Enemy> = app.world_mut().spawn_key_as("goblin").unwrap();
+    /// ```
+    ///
+    /// If the key is invalid (see [`SpawnWorld::try_spawn_key`]), no [`Entity`] is spawned and a [`SpawnError`]
+    /// is returned instead. If the spawned entity does not have the component once spawned,
+    /// it is despawned, along with its children, and a [`SpawnError::MissingComponent`] is returned.
+    ///
+    /// This function is only available with the `kind` feature.
+    #[cfg(feature = "kind")]
+    fn spawn_key_as<T: Component>(
+        &mut self,
+        key: impl Into<SpawnKey>,
+    ) -> Result<SpawnInstance<T>, SpawnError>;
+
+    /// Spawns a spawnable via a [`SpawnKey`] only if no [`Entity`] has been spawned with the given identity yet.
+    ///
+    /// If an entity was already spawned with the same identity and it still exists, it is returned instead.
+    /// This is useful for idempotent spawns, such as replicated entities which may be requested more than once.
+    ///
+    /// Each identity type has its own [`SpawnIdentities`] resource.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use moonshine_spawn::prelude::*;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Player;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, SpawnPlugin));
+    /// app.add_spawnable("player", Player);
+    ///
+    /// let world = app.world_mut();
+    /// let a = world.spawn_key_unique(7_u64, "player").id();
+    /// let b = world.spawn_key_unique(7_u64, "player").id();
+    /// assert_eq!(a, b);
+    /// ```
+    fn spawn_key_unique<I: Hash + Eq + Send + Sync + 'static>(
+        &mut self,
+        identity: I,
+        key: impl Into<SpawnKey>,
+    ) -> EntityWorldMut<'_>;
+
+    /// Despawns all descendants of the given [`Entity`] and spawns the spawnable registered with the given
+    /// [`SpawnKey`] into it again.
+    ///
+    /// This is useful for reloading spawnables which have been replaced (see [`Spawnables::replace`]).
+    /// The [`Entity`] itself is preserved, along with any of its components which are not inserted by the spawnable,
+    /// such as a `Transform` given at the call site. However, components which are no longer inserted by the
+    /// spawnable are not removed.
+    fn respawn_key(&mut self, entity: Entity, key: impl Into<SpawnKey>) -> EntityWorldMut<'_>;
+
+    /// Same as [`SpawnWorld::respawn_key`], using the [`SpawnSource`] of the given [`Entity`] as the key.
+    ///
+    /// # Warning
+    /// This function will panic if the entity was not spawned via a [`SpawnKey`].
+    fn respawn(&mut self, entity: Entity) -> EntityWorldMut<'_>;
+
+    /// Recursively despawns every [`Entity`] spawned via the given [`SpawnKey`], and returns how many were despawned.
+    ///
+    /// Only root entities of spawned hierarchies are considered. That is, an entity is despawned only if its [`SpawnSource`]
+    /// matches the given key, it was not spawned as a child from [`SpawnChildren`], and none of its ancestors have a
+    /// [`SpawnSource`]. Children spawned with their own keys are not despawned by their key, since they belong to the
+    /// hierarchy of their parent, even if the parent was spawned without a key (i.e. [`SpawnWorld::spawn_with`]).
+    ///
+    /// This requires [`SpawnConfig::record_spawn_source`] to be enabled.
+    fn despawn_all_with_key(&mut self, key: impl Into<SpawnKey>) -> usize;
+
+    /// Recursively despawns every [`Entity`] tagged with the given [`SpawnKind`], and returns how many were despawned.
+    ///
+    /// Like [`SpawnWorld::despawn_all_with_key`], only root entities of spawned hierarchies are considered.
+    fn despawn_all_of_kind(&mut self, kind: impl Into<SpawnKind>) -> usize;
+}
+
+impl SpawnWorld for World {
+    fn spawn_with(&mut self, spawnable: impl Spawn) -> EntityWorldMut<'_> {
+        let entity = spawn_root(self);
+        Spawnable::spawn(&spawnable, self, entity);
+        invoke_spawn_children(self);
+        self.entity_mut(entity)
+    }
+
+    fn spawn_once_with(&mut self, spawnable: impl SpawnOnce) -> EntityWorldMut<'_> {
+        let entity = spawn_root(self);
+        SpawnableOnce::spawn_once(spawnable, self, entity);
+        invoke_spawn_children(self);
+        self.entity_mut(entity)
+    }
+
+    fn spawn_batch_with(&mut self, count: usize, spawnable: impl Spawn) -> Vec<Entity> {
+        let entities: Vec<Entity> = (0..count).map(|_| spawn_root(self)).collect();
+        spawn_batch_into(self, &entities, &spawnable);
+        invoke_spawn_children(self);
+        entities
+    }
+
+    fn spawn_once_try_with(&mut self, spawnable: impl TrySpawnOnce) -> Option<EntityWorldMut<'_>> {
+        let entity = spawn_root(self);
+        if !try_spawn_once(spawnable, self, entity) {
+            return None;
+        }
+        invoke_spawn_children(self);
+        Some(self.entity_mut(entity))
+    }
+
+    fn spawn_key(&mut self, key: impl Into<SpawnKeyRef>) -> EntityWorldMut<'_> {
+        let key: SpawnKeyRef = key.into();
+        let entity = spawn_root(self);
+        key.spawn_once(self, entity);
+        invoke_spawn_children(self);
+        self.entity_mut(entity)
+    }
+
+    fn spawn_key_deferred(&mut self, key: impl Into<SpawnKey>) -> EntityWorldMut<'_> {
+        let key: SpawnKey = key.into();
+        let entity = spawn_root(self);
+        key.spawn_once(self, entity);
+        self.entity_mut(entity)
+    }
+
+    fn spawn_key_collect(&mut self, key: impl Into<SpawnKey>) -> (Entity, Vec<Entity>) {
+        let key: SpawnKey = key.into();
+        let entity = spawn_root(self);
+        key.spawn_once(self, entity);
+        let mut children = Vec::new();
+        invoke_spawn_children_with(self, |parent, child| {
+            if parent == entity {
+                children.push(child);
+            }
+        });
+        (entity, children)
+    }
+
+    fn spawn_key_child(&mut self, parent: Entity, key: impl Into<SpawnKey>) -> EntityWorldMut<'_> {
+        let key: SpawnKey = key.into();
+        let child = self.spawn_empty().id();
+        self.entity_mut(parent).add_child(child);
+        key.spawn_once(self, child);
+        invoke_spawn_children(self);
+        self.entity_mut(child)
+    }
+
+    fn spawn_key_at(&mut self, entity: Entity, key: impl Into<SpawnKey>) -> EntityWorldMut<'_> {
+        let key: SpawnKey = key.into();
+        // Reserved entities are only allocated once the world is flushed:
+        self.flush();
+        assert!(
+            self.get_entity(entity).is_ok(),
+            "{entity:?} must be reserved to spawn {key:?}"
+        );
+        key.spawn_once(self, entity);
+        invoke_spawn_children(self);
+        self.entity_mut(entity)
+    }
+
+    fn try_spawn_key(
+        &mut self,
+        key: impl Into<SpawnKeyRef>,
+    ) -> Result<EntityWorldMut<'_>, SpawnError> {
+        let key: SpawnKeyRef = key.into();
+        key.check(self.resource::<Spawnables>())?;
+        let entity = spawn_root(self);
+        key.spawn_once(self, entity);
+        invoke_spawn_children(self);
+        Ok(self.entity_mut(entity))
+    }
+
+    fn spawn_key_with(
+        &mut self,
+        key: impl Into<SpawnKey>,
+        bundle: impl Bundle,
+    ) -> EntityWorldMut<'_> {
+        let key = key.into();
+        let entity = spawn_root(self);
+        SpawnKeyWith(KeyedChild::new(key), bundle).spawn_once(self, entity);
+        invoke_spawn_children(self);
+        self.entity_mut(entity)
+    }
+
+    fn spawn_key_with_children(
+        &mut self,
+        key: impl Into<SpawnKey>,
+        f: impl FnOnce(&mut SpawnChildBuilder),
+    ) -> EntityWorldMut<'_> {
+        let entity = spawn_root(self);
+        SpawnKeyWithChildren(KeyedChild::new(key), spawn_children(f)).spawn_once(self, entity);
+        invoke_spawn_children(self);
+        self.entity_mut(entity)
+    }
+
+    fn spawn_key_with_params<P: 'static + Send + Sync>(
+        &mut self,
+        key: impl Into<SpawnKey>,
+        params: P,
+    ) -> EntityWorldMut<'_> {
+        let key = key.into();
+        let entity = spawn_root(self);
+        SpawnKeyWithParams(KeyedChild::new(key), params).spawn_once(self, entity);
+        invoke_spawn_children(self);
+        self.entity_mut(entity)
+    }
+
+    fn respawn_key(&mut self, entity: Entity, key: impl Into<SpawnKey>) -> EntityWorldMut<'_> {
+        let key: SpawnKey = key.into();
+        self.entity_mut(entity).despawn_descendants();
+        key.spawn_once(self, entity);
+        invoke_spawn_children(self);
+        self.entity_mut(entity)
+    }
+
+    fn respawn(&mut self, entity: Entity) -> EntityWorldMut<'_> {
+        let Some(SpawnSource(key)) = self.get::<SpawnSource>(entity).cloned() else {
+            panic!("{entity:?} was not spawned via a spawn key");
+        };
+        self.entity_mut(entity).despawn_descendants();
+        key.spawn_once(self, entity);
+        invoke_spawn_children(self);
+        self.entity_mut(entity)
+    }
+
+    #[cfg(feature = "kind")]
+    fn spawn_key_as<T: Component>(
+        &mut self,
+        key: impl Into<SpawnKey>,
+    ) -> Result<SpawnInstance<T>, SpawnError> {
+        let key: SpawnKey = key.into();
+        let entity = self.try_spawn_key(key.clone())?.id();
+        let result = check_instance(self, entity, &key);
+        if result.is_err() {
+            self.entity_mut(entity).despawn_recursive();
+        }
+        result
+    }
+
+    fn spawn_key_unique<I: Hash + Eq + Send + Sync + 'static>(
+        &mut self,
+        identity: I,
+        key: impl Into<SpawnKey>,
+    ) -> EntityWorldMut<'_> {
+        if let Some(entity) = spawned_identity(self, &identity) {
+            return self.entity_mut(entity);
+        }
+        let entity = self.spawn_key(key).id();
+        self.get_resource_or_insert_with(SpawnIdentities::<I>::default)
+            .0
+            .insert(identity, entity);
+        self.entity_mut(entity)
+    }
+
+    fn despawn_all_with_key(&mut self, key: impl Into<SpawnKey>) -> usize {
+        let key = key.into();
+        let spawnables = self.resource::<Spawnables>();
+        let key = spawnables.lookup_key(&key).into_owned();
+        let interned = spawnables.interned.get(&key).copied();
+        despawn_roots_where(self, |source: &SpawnSource| match source.key() {
+            SpawnKeyRef::Key(other) => key.finds(other),
+            SpawnKeyRef::Interned(other) => Some(*other) == interned,
+        })
+    }
+
+    fn despawn_all_of_kind(&mut self, kind: impl Into<SpawnKind>) -> usize {
+        let kind = kind.into();
+        despawn_roots_where(self, |other: &SpawnKind| other == &kind)
+    }
+}
+
+/// A [`Component`] which marks a keyed [`Entity`] spawned as a child from [`SpawnChildren`].
+///
+/// Such entities belong to the hierarchy of their parent, and are never considered roots (see [`despawn_roots_where`]).
+/// It is only inserted along with the [`SpawnSource`] or [`SpawnKind`] of the child, so it never moves the child
+/// into another archetype by itself. Children without either component are never matched as roots anyway.
+#[derive(Component)]
+#[component(storage = "SparseSet")]
+struct SpawnedChild;
+
+/// A [`Resource`] which tracks the [`Entity`] currently being spawned as a child from [`SpawnChildren`], if any.
+#[derive(Resource, Default)]
+struct SpawningChild(Option<Entity>);
+
+impl SpawningChild {
+    /// Marks the given child as being spawned, and returns the previously marked child.
+    fn begin(world: &mut World, child: Entity) -> Option<Entity> {
+        world
+            .get_resource_or_insert_with(Self::default)
+            .0
+            .replace(child)
+    }
+
+    /// Restores the previously marked child once the current child is spawned.
+    fn end(world: &mut World, previous: Option<Entity>) {
+        world.resource_mut::<Self>().0 = previous;
+    }
+
+    /// Returns `true` if the given [`Entity`] is being spawned as a child from [`SpawnChildren`].
+    fn contains(world: &World, entity: Entity) -> bool {
+        world
+            .get_resource::<Self>()
+            .is_some_and(|child| child.0 == Some(entity))
+    }
+}
+
+/// Recursively despawns every root [`Entity`] with a component `T` which matches the given predicate,
+/// unless one of its ancestors also has a component `T`. Returns how many were despawned.
+///
+/// Children spawned from [`SpawnChildren`] are never roots.
+fn despawn_roots_where<T: Component>(world: &mut World, predicate: impl Fn(&T) -> bool) -> usize {
+    let entities: Vec<_> = world
+        .query_filtered::<(Entity, &T), Without<SpawnedChild>>()
+        .iter(world)
+        .filter(|(_, component)| predicate(component))
+        .map(|(entity, _)| entity)
+        .filter(|&entity| {
+            std::iter::successors(world.get::<Parent>(entity), |parent| {
+                world.get::<Parent>(parent.get())
+            })
+            .all(|parent| !world.entity(parent.get()).contains::<T>())
+        })
+        .collect();
+    for &entity in &entities {
+        world.entity_mut(entity).despawn_recursive();
+    }
+    entities.len()
+}
+
+/// Trait used to spawn spawnables via a [`SpawnKey`] into an existing [`Entity`] using [`EntityCommands`].
+pub trait SpawnEntityCommands {
+    /// Spawns the spawnable registered with the given [`SpawnKey`] into this entity.
+    ///
+    /// Unlike [`SpawnCommands::spawn_key`], this does not spawn a new [`Entity`].
+    /// Any [`SpawnChildren`] inserted by the spawnable are spawned as children of this entity.
+    fn insert_spawn_key(&mut self, key: impl Into<SpawnKey>) -> &mut Self;
+
+    /// Spawns the spawnable registered with the given [`SpawnKey`] as a child of this entity.
+    ///
+    /// See [`SpawnWorld::spawn_key_child`] for details.
+    fn with_spawn_key(&mut self, key: impl Into<SpawnKey>) -> &mut Self;
+
+    /// Adds children to be spawned for this entity, after any existing [`SpawnChildren`] of this entity.
+    ///
+    /// Unlike inserting a [`SpawnChildren`] component, this does not replace any children which are already pending.
+    /// The children are spawned during the next spawn system, like any other [`SpawnChildren`].
+    ///
+    /// This is useful to spawn children from a component hook, where only deferred access to the world is available.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy::ecs::{component::ComponentId, world::DeferredWorld};
+    /// # use moonshine_spawn::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// #[component(on_add = add_barrel)]
+    /// struct Turret;
+    ///
+    /// #[derive(Component)]
+    /// struct Barrel;
+    ///
+    /// fn add_barrel(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+    ///     world.commands().entity(entity).add_spawn_children(|turret| {
+    ///         turret.spawn(Barrel);
+    ///     });
+    /// }
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, SpawnPlugin));
+    /// let turret = app.world_mut().spawn(Turret).id();
+    /// app.world_mut().flush();
+    /// app.update();
+    ///
+    /// let barrel = app.world().get::<Children>(turret).unwrap()[0];
+    /// assert!(app.world().entity(barrel).contains::<Barrel>());
+    /// ```
+    fn add_spawn_children(&mut self, f: impl FnOnce(&mut SpawnChildBuilder)) -> &mut Self;
+}
+
+impl SpawnEntityCommands for EntityCommands<'_> {
+    fn add_spawn_children(&mut self, f: impl FnOnce(&mut SpawnChildBuilder)) -> &mut Self {
+        let children = spawn_children(f);
+        self.queue(move |mut entity: EntityWorldMut| {
+            let mut pending = entity.take::<SpawnChildren>().unwrap_or_default();
+            pending.extend(children);
+            entity.insert(pending);
+        })
+    }
+
+    fn insert_spawn_key(&mut self, key: impl Into<SpawnKey>) -> &mut Self {
+        let key: SpawnKey = key.into();
+        self.queue(move |entity: Entity, world: &mut World| {
+            key.spawn_once(world, entity);
+        })
+    }
+
+    fn with_spawn_key(&mut self, key: impl Into<SpawnKey>) -> &mut Self {
+        let parent = self.id();
+        self.commands().spawn_key_child(parent, key);
+        self
+    }
+}
+
+/// Trait used to spawn spawnables via a [`SpawnKey`] into an existing [`Entity`] using [`EntityWorldMut`].
+pub trait SpawnEntityWorld {
+    /// Spawns the spawnable registered with the given [`SpawnKey`] into this entity.
+    ///
+    /// Unlike [`SpawnWorld::spawn_key`], this does not spawn a new [`Entity`].
+    /// Any [`SpawnChildren`] inserted by the spawnable are spawned as children of this entity.
+    fn insert_spawn_key(&mut self, key: impl Into<SpawnKey>) -> &mut Self;
+
+    /// Spawns the spawnable registered with the given [`SpawnKey`] as a child of this entity.
+    ///
+    /// See [`SpawnWorld::spawn_key_child`] for details.
+    fn with_spawn_key(&mut self, key: impl Into<SpawnKey>) -> &mut Self;
+
+    /// Adds more children to the pending [`SpawnChildren`] of this entity, or inserts a new one if none are pending.
+    ///
+    /// Like [`SpawnEntityCommands::add_spawn_children`], the new children are spawned after any pending children.
+    /// Note that they are spawned by the spawn system, unless spawned manually (see [`SpawnEntityWorld::spawn_pending_children`]).
+    fn add_spawn_children(&mut self, f: impl FnOnce(&mut SpawnChildBuilder)) -> &mut Self;
+
+    /// Immediately spawns all pending [`SpawnChildren`] of this entity, and any of its descendants.
+    ///
+    /// See [`force_spawn_children_for`] for details.
+    fn spawn_pending_children(&mut self) -> &mut Self;
+}
+
+impl SpawnEntityWorld for EntityWorldMut<'_> {
+    fn insert_spawn_key(&mut self, key: impl Into<SpawnKey>) -> &mut Self {
+        let key: SpawnKey = key.into();
+        let entity = self.id();
+        self.world_scope(|world| {
+            key.spawn_once(world, entity);
+            invoke_spawn_children(world);
+        });
+        self
+    }
+
+    fn with_spawn_key(&mut self, key: impl Into<SpawnKey>) -> &mut Self {
+        let parent = self.id();
+        self.world_scope(|world| {
+            world.spawn_key_child(parent, key);
+        });
+        self
+    }
+
+    fn add_spawn_children(&mut self, f: impl FnOnce(&mut SpawnChildBuilder)) -> &mut Self {
+        let mut pending = self.take::<SpawnChildren>().unwrap_or_default();
+        pending.extend(spawn_children(f));
+        self.insert(pending)
+    }
+
+    fn spawn_pending_children(&mut self) -> &mut Self {
+        let entity = self.id();
+        self.world_scope(|world| {
+            invoke_spawn_children_of(world, |_, roots| roots.push(entity));
+        });
+        self
+    }
+}
+
+fn validate_key(key: &SpawnKey) -> Result<(), SpawnRegisterError> {
+    if !key.is_valid() {
+        return Err(SpawnRegisterError::InvalidKey(key.clone()));
+    }
+    if key.name().trim() != key.name() {
+        warn!("spawn key has leading or trailing whitespace: {key:?}");
+    }
+    Ok(())
+}
+
+/// A [`Resource`] which contains all registered spawnables.
+#[derive(Resource, Default, Clone)]
+pub struct Spawnables {
+    entries: HashMap<SpawnKey, Arc<dyn Spawnable>>,
+    interned: HashMap<SpawnKey, InternedSpawnKey>,
+    slots: Vec<(SpawnKey, Option<Arc<dyn Spawnable>>)>,
+    kinds: HashMap<SpawnKey, SpawnKind>,
+    /// Lowercase names of all keys registered as case-insensitive (see [`SpawnKey::new_ci`]).
+    case_insensitive: HashSet<String>,
+}
+
+impl Spawnables {
+    /// Registers a spawnable with a unique [`SpawnKey`] and returns it.
+    ///
+    /// # Warning
+    /// This function will panic if the given key is already registered or invalid (see [`SpawnKey::is_valid`]).
+    /// Use [`Spawnables::replace`] to overwrite an existing spawnable instead,
+    /// or [`Spawnables::try_register`] to handle duplicate keys gracefully.
+    pub fn register<T>(&mut self, key: impl Into<SpawnKey>, spawnable: T) -> SpawnKey
+    where
+        T: Spawnable,
+    {
+        self.try_register(key, spawnable)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Registers a spawnable with a unique [`SpawnKey`] and returns it, or returns an error if the key is already registered.
+    ///
+    /// Unlike [`Spawnables::register`], this function does not panic. If the key is already registered,
+    /// the existing spawnable is kept and the given spawnable is discarded.
+    pub fn try_register<T>(
+        &mut self,
+        key: impl Into<SpawnKey>,
+        spawnable: T,
+    ) -> Result<SpawnKey, SpawnRegisterError>
+    where
+        T: Spawnable,
+    {
+        let key = key.into();
+        validate_key(&key)?;
+        if self.contains(&key) {
+            return Err(SpawnRegisterError::DuplicateKey(key));
+        }
+        self.insert(key.clone(), Arc::new(spawnable));
+        Ok(key)
+    }
+
+    /// Registers many spawnables, each with a unique [`SpawnKey`], and returns their keys in the same order.
+    ///
+    /// # Warning
+    /// This function will panic if any of the given keys are already registered, or given more than once.
+    /// In this case, all colliding keys are reported at once, and none of the spawnables are registered.
+    ///
+    /// # Example
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use bevy::prelude::*;
+    /// # use moonshine_spawn::prelude::*;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Chicken;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Egg;
+    ///
+    /// let mut spawnables = Spawnables::default();
+    /// let keys = spawnables.register_many([
+    ///     ("chicken", Arc::new(Chicken) as Arc<dyn Spawnable>),
+    ///     ("egg", Arc::new(Egg)),
+    /// ]);
+    /// assert_eq!(keys.len(), 2);
+    /// ```
+    pub fn register_many<K: Into<SpawnKey>>(
+        &mut self,
+        entries: impl IntoIterator<Item = (K, Arc<dyn Spawnable>)>,
+    ) -> Vec<SpawnKey> {
+        let entries: Vec<_> = entries
+            .into_iter()
+            .map(|(key, spawnable)| (key.into(), spawnable))
+            .collect();
+        for (key, _) in &entries {
+            validate_key(key).unwrap_or_else(|error| panic!("{error}"));
+        }
+
+        let mut keys = HashSet::new();
+        let collisions: Vec<_> = entries
+            .iter()
+            .map(|(key, _)| key.clone())
+            .filter(|key| self.contains(key) || !keys.insert(key.clone()))
+            .collect();
+        assert!(
+            collisions.is_empty(),
+            "spawn keys must be unique: {collisions:?}"
+        );
+
+        entries
+            .into_iter()
+            .map(|(key, spawnable)| {
+                self.insert(key.clone(), spawnable);
+                key
+            })
+            .collect()
+    }
+
+    /// Registers a [`ParametricSpawn`] with a unique [`SpawnKey`] and returns it.
+    ///
+    /// A parametric spawnable may only be spawned with parameters of exactly type `P`, using `spawn_key_with_params`.
+    /// Spawning it without parameters or with parameters of a different type will panic.
+    ///
+    /// # Warning
+    /// This function will panic if the given key is already registered.
+    pub fn register_parametric<P, T>(&mut self, key: impl Into<SpawnKey>, spawnable: T) -> SpawnKey
+    where
+        P: 'static + Send + Sync,
+        T: ParametricSpawn<P>,
+    {
+        let key = key.into();
+        let spawnable = ParametricSpawnable::<P>(Box::new(move |params, world, entity| {
+            let bundle = spawnable.spawn(params, world, entity);
+            insert_spawned(world, entity, bundle);
+        }));
+        validate_key(&key).unwrap_or_else(|error| panic!("{error}"));
+        let previous = self.insert(key.clone(), Arc::new(spawnable));
+        assert!(previous.is_none(), "spawn key must be unique: {key:?}",);
+        key
+    }
+
+    /// Registers a [`SpawnWithParam`] with a unique [`SpawnKey`] and returns it.
+    ///
+    /// # Warning
+    /// This function will panic if the given key is already registered.
+    pub fn register_with_param<T>(&mut self, key: impl Into<SpawnKey>, spawnable: T) -> SpawnKey
+    where
+        T: SpawnWithParam,
+    {
+        let key = key.into();
+        let spawnable = SpawnableWithParam {
+            spawnable,
+            state: Mutex::new(None),
+        };
+        validate_key(&key).unwrap_or_else(|error| panic!("{error}"));
+        let previous = self.insert(key.clone(), Arc::new(spawnable));
+        assert!(previous.is_none(), "spawn key must be unique: {key:?}",);
+        key
+    }
+
+    /// Registers an alias for the spawnable registered with an existing [`SpawnKey`], and returns the alias.
+    ///
+    /// Both keys share the same spawnable, so spawning either key spawns the same thing. This is useful to rename
+    /// a spawnable while keeping old references valid. Each entity still records the key it was spawned with as its
+    /// [`SpawnSource`]. Replacing or unregistering either key later does not affect the other.
+    ///
+    /// Returns an error if the alias is already registered or invalid, or if the existing key is not registered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use moonshine_spawn::prelude::*;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Player;
+    ///
+    /// let mut spawnables = Spawnables::default();
+    /// spawnables.register("player", Player);
+    /// spawnables.register_alias("hero", "player").unwrap();
+    /// assert!(spawnables.contains(&"hero".into()));
+    /// ```
+    pub fn register_alias(
+        &mut self,
+        alias: impl Into<SpawnKey>,
+        key: impl Into<SpawnKey>,
+    ) -> Result<SpawnKey, SpawnRegisterError> {
+        let alias = alias.into();
+        let key = key.into();
+        validate_key(&alias)?;
+        if self.contains(&alias) {
+            return Err(SpawnRegisterError::DuplicateKey(alias));
+        }
+        let Some(spawnable) = self.get(&key) else {
+            return Err(SpawnRegisterError::MissingKey(key));
+        };
+        self.insert(alias.clone(), spawnable);
+        Ok(alias)
+    }
+
+    /// Registers a [`ReflectSpawnable`] with a unique [`SpawnKey`] and returns it.
+    ///
+    /// # Warning
+    /// This function will panic if the given key is already registered.
+    pub fn register_reflect(
+        &mut self,
+        key: impl Into<SpawnKey>,
+        spawnable: ReflectSpawnable,
+    ) -> SpawnKey {
+        let key = key.into();
+        validate_key(&key).unwrap_or_else(|error| panic!("{error}"));
+        let previous = self.insert(key.clone(), Arc::new(spawnable));
+        assert!(previous.is_none(), "spawn key must be unique: {key:?}",);
+        key
+    }
+
+    /// Registers a spawnable with the given [`SpawnKey`] only if the key is not already registered, and returns it.
+    ///
+    /// Unlike [`Spawnables::register`], this function does not panic if the key is already registered.
+    /// Instead, the existing spawnable is kept and the given spawnable is discarded.
+    /// This is useful for idempotent setup code which may run multiple times.
+    ///
+    /// # Warning
+    /// This function will panic if the given key is invalid (see [`SpawnKey::is_valid`]).
+    pub fn get_or_register<T>(&mut self, key: impl Into<SpawnKey>, spawnable: T) -> SpawnKey
+    where
+        T: Spawnable,
+    {
+        let key = key.into();
+        validate_key(&key).unwrap_or_else(|error| panic!("{error}"));
+        if !self.contains(&key) {
+            self.insert(key.clone(), Arc::new(spawnable));
+        }
+        key
+    }
+
+    /// Registers a spawnable with the given [`SpawnKey`], replacing any spawnable previously registered with it.
+    ///
+    /// Unlike [`Spawnables::register`], this function does not panic if the key is already registered.
+    /// Instead, it returns the previous spawnable, if any. This is useful for hot-reloading spawnables.
+    ///
+    /// # Warning
+    /// This function will panic if the given key is invalid (see [`SpawnKey::is_valid`]).
+    pub fn replace<T>(
+        &mut self,
+        key: impl Into<SpawnKey>,
+        spawnable: T,
+    ) -> Option<Arc<dyn Spawnable>>
+    where
+        T: Spawnable,
+    {
+        let key = key.into();
+        validate_key(&key).unwrap_or_else(|error| panic!("{error}"));
+        self.insert(key, Arc::new(spawnable))
+    }
+
+    /// Removes the spawnable registered with the given [`SpawnKey`] and returns it, if any.
+    ///
+    /// Any subsequent spawns using this key will fail until it is registered again.
+    pub fn unregister(&mut self, key: &SpawnKey) -> Option<Arc<dyn Spawnable>> {
+        self.remove(key)
+    }
+
+    /// Returns the number of registered spawnables.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no spawnables are registered.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns `true` if the given [`SpawnKey`] is registered.
+    pub fn contains(&self, key: &SpawnKey) -> bool {
+        self.entries.contains_key(&*self.lookup_key(key))
+    }
+
+    /// Returns an iterator over all registered [`SpawnKey`]s.
+    pub fn keys(&self) -> impl Iterator<Item = &SpawnKey> {
+        self.entries.keys()
+    }
+
+    /// Returns a list of all registered [`SpawnKey`]s, sorted by name.
+    ///
+    /// Unlike [`Spawnables::keys`], the order of the returned keys is deterministic.
+    /// This is useful for debugging and tooling.
+    pub fn keys_sorted(&self) -> Vec<&SpawnKey> {
+        let mut keys: Vec<_> = self.keys().collect();
+        keys.sort_unstable_by(|a, b| a.name().cmp(b.name()));
+        keys
+    }
+
+    /// Checks that all resources required by registered spawnables exist in the given [`World`], without spawning anything.
+    ///
+    /// This is useful to detect missing resources during startup, rather than when a spawnable is first spawned.
+    /// Only resources declared by [`Spawn::required_resources`] are checked.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use moonshine_spawn::prelude::*;
+    ///
+    /// #[derive(Resource)]
+    /// struct ChickenAssets;
+    ///
+    /// struct Chicken;
+    ///
+    /// impl Spawn for Chicken {
+    ///     type Output = Name;
+    ///
+    ///     fn spawn(&self, world: &World, _: Entity) -> Name {
+    ///         world.resource::<ChickenAssets>();
+    ///         Name::new("Chicken")
+    ///     }
+    ///
+    ///     fn required_resources(&self) -> Vec<RequiredResource> {
+    ///         vec![RequiredResource::of::<ChickenAssets>()]
+    ///     }
+    /// }
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, SpawnPlugin));
+    /// app.add_spawnable("chicken", Chicken);
+    ///
+    /// let world = app.world();
+    /// assert!(world.resource::<Spawnables>().validate(world).is_err());
+    /// ```
+    pub fn validate(&self, world: &World) -> Result<(), Vec<SpawnError>> {
+        let mut errors: Vec<_> = self
+            .entries
+            .iter()
+            .flat_map(|(key, spawnable)| {
+                spawnable
+                    .required_resources()
+                    .into_iter()
+                    .filter(|resource| !resource.exists(world))
+                    .map(|resource| SpawnError::MissingResource(key.clone(), resource.name()))
+            })
+            .collect();
+        if errors.is_empty() {
+            return Ok(());
+        }
+        errors.sort_by_key(|error| error.to_string());
+        Err(errors)
+    }
+
+    /// Returns an iterator over all registered [`SpawnKey`]s with names which start with the given prefix.
+    ///
+    /// The prefix is matched as a plain string. To match keys within a namespace, include the separator
+    /// (i.e. `"enemies/"` rather than `"enemies"`). See [`SpawnKey::namespace`] for details.
+    pub fn keys_with_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a SpawnKey> {
+        self.keys()
+            .filter(move |key| key.name().starts_with(prefix))
+    }
+
+    /// Spawns the spawnable registered with the given [`SpawnKey`] into the given [`World`] and returns its [`Entity`].
+    ///
+    /// This is useful for spawning into a world which does not contain this registry, such as a `SubApp` world.
+    /// Because the registry is borrowed while spawning, the target world must not be the world which contains it.
+    ///
+    /// While spawning, these spawnables are temporarily moved into the target world, replacing its own
+    /// [`Spawnables`] if it has any, so that children spawned via a [`SpawnKey`], at any depth, are resolved from
+    /// this registry. The registry is moved back once spawning completes, even if spawning panics,
+    /// so it is never cloned or lost.
+    ///
+    /// See [`Spawnables::spawn_into_with_params`] to spawn a [`ParametricSpawn`].
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use moonshine_spawn::prelude::*;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Chicken;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, SpawnPlugin));
+    /// app.add_spawnable("chicken", Chicken);
+    ///
+    /// let mut other = World::new();
+    /// let mut spawnables = app.world_mut().resource_mut::<Spawnables>();
+    /// let chicken = spawnables.spawn_into(&mut other, "chicken").unwrap();
+    /// assert!(other.entity(chicken).contains::<Chicken>());
+    /// ```
+    pub fn spawn_into(
+        &mut self,
+        world: &mut World,
+        key: impl Into<SpawnKey>,
+    ) -> Result<Entity, SpawnError> {
+        let key = key.into();
+        self.lend(world, |world| {
+            world.try_spawn_key(key).map(|entity| entity.id())
+        })
+    }
+
+    /// Spawns the [`ParametricSpawn`] registered with the given [`SpawnKey`] into the given [`World`] using the
+    /// given parameters, and returns its [`Entity`].
+    ///
+    /// If the key is not registered, or does not accept parameters of type `P`, no [`Entity`] is spawned
+    /// and a [`SpawnError`] is returned instead. See [`Spawnables::spawn_into`] for details.
+    pub fn spawn_into_with_params<P: 'static + Send + Sync>(
+        &mut self,
+        world: &mut World,
+        key: impl Into<SpawnKey>,
+        params: P,
+    ) -> Result<Entity, SpawnError> {
+        let key = key.into();
+        let Some(spawnable) = self.get(&key) else {
+            return Err(SpawnError::InvalidKey(key));
+        };
+        if !spawnable
+            .as_parametric()
+            .is_some_and(|spawnable| spawnable.is::<ParametricSpawnable<P>>())
+        {
+            return Err(SpawnError::InvalidParams(key, type_name::<P>()));
+        }
+        Ok(self.lend(world, |world| world.spawn_key_with_params(key, params).id()))
+    }
+
+    /// Moves this registry into the given [`World`] while calling `f`, and moves it back once `f` returns or panics.
+    fn lend<R>(&mut self, world: &mut World, f: impl FnOnce(&mut World) -> R) -> R {
+        let previous = world.remove_resource::<Spawnables>();
+        world.insert_resource(std::mem::take(self));
+        let result = panic::catch_unwind(AssertUnwindSafe(|| f(world)));
+        *self = world.remove_resource::<Spawnables>().unwrap_or_default();
+        if let Some(previous) = previous {
+            world.insert_resource(previous);
+        }
+        result.unwrap_or_else(|panic| panic::resume_unwind(panic))
+    }
+
+    /// Returns the spawnable registered with the given [`SpawnKey`], if any.
+    ///
+    /// This is useful for advanced cases, such as invoking a spawnable manually against an existing [`Entity`],
+    /// or wrapping it in another spawnable. Note that invoking a spawnable directly only inserts its output
+    /// into the entity. Unlike [`SpawnWorld::spawn_key`], it does not insert a [`SpawnSource`] or spawn any children.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use moonshine_spawn::prelude::*;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Chicken;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, SpawnPlugin));
+    /// app.add_spawnable("chicken", Chicken);
+    ///
+    /// let world = app.world_mut();
+    /// let spawnable = world.resource::<Spawnables>().get(&"chicken".into()).unwrap();
+    /// let entity = world.spawn_empty().id();
+    /// spawnable.spawn(world, entity);
+    /// assert!(world.entity(entity).contains::<Chicken>());
+    /// ```
+    pub fn get(&self, key: &SpawnKey) -> Option<Arc<dyn Spawnable>> {
+        self.entries.get(&*self.lookup_key(key)).cloned()
+    }
+
+    /// Returns the spawnable registered with the given [`SpawnKey`] if it may be spawned without parameters.
+    fn try_get(&self, key: &SpawnKey) -> Result<Arc<dyn Spawnable>, SpawnError> {
+        let Some(spawnable) = self.get(key) else {
+            return Err(SpawnError::InvalidKey(key.clone()));
+        };
+        if spawnable.as_parametric().is_some() {
+            return Err(SpawnError::MissingParams(key.clone()));
+        }
+        Ok(spawnable)
+    }
+
+    /// Returns the components which the spawnable registered with the given [`SpawnKey`] inserts, without spawning it.
+    ///
+    /// This is useful for tooling, such as to show or validate the components of a spawnable before it is spawned.
+    /// Returns `None` if the key is not registered, or if its components are not known ahead of time
+    /// (see [`Spawn::output_components`]). Note that children and components inserted by children are not included.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use moonshine_spawn::prelude::*;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Chicken;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, SpawnPlugin));
+    /// app.add_spawnable("chicken", (Chicken, Transform::default()));
+    ///
+    /// let world = app.world_mut();
+    /// let components = Spawnables::components_of(world, "chicken").unwrap();
+    /// assert!(components.contains(&world.register_component::<Transform>()));
+    /// ```
+    pub fn components_of(world: &mut World, key: impl Into<SpawnKey>) -> Option<Vec<ComponentId>> {
+        let spawnable = world.resource::<Spawnables>().get(&key.into())?;
+        spawnable.output_components(world)
+    }
+
+    /// Assigns a [`SpawnKind`] to the given [`SpawnKey`] and returns the previous kind, if any.
+    ///
+    /// Every entity spawned via this key is tagged with its kind, which may be used to query or despawn
+    /// all entities of the same kind (see [`SpawnWorld::despawn_all_of_kind`]).
+    /// The key does not need to be registered yet. Each key may only have one kind.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use moonshine_spawn::prelude::*;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Button;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, SpawnPlugin));
+    /// app.add_spawnable("button", Button);
+    ///
+    /// let world = app.world_mut();
+    /// world.resource_mut::<Spawnables>().set_kind("button", "ui");
+    /// let button = world.spawn_key("button").id();
+    /// assert_eq!(world.get::<SpawnKind>(button), Some(&SpawnKind::new("ui")));
+    /// assert_eq!(world.despawn_all_of_kind("ui"), 1);
+    /// ```
+    pub fn set_kind(
+        &mut self,
+        key: impl Into<SpawnKey>,
+        kind: impl Into<SpawnKind>,
+    ) -> Option<SpawnKind> {
+        let key = key.into();
+        let key = self.lookup_key(&key).into_owned();
+        self.kinds.insert(key, kind.into())
+    }
+
+    /// Removes the [`SpawnKind`] of the given [`SpawnKey`] and returns it, if any.
+    pub fn clear_kind(&mut self, key: &SpawnKey) -> Option<SpawnKind> {
+        let key = self.lookup_key(key).into_owned();
+        self.kinds.remove(&key)
+    }
+
+    /// Returns the [`SpawnKind`] of the given [`SpawnKey`], if any.
+    pub fn kind_of(&self, key: &SpawnKey) -> Option<&SpawnKind> {
+        self.kinds.get(&*self.lookup_key(key))
+    }
+
+    /// Returns an iterator over all [`SpawnKey`]s with the given [`SpawnKind`].
+    pub fn keys_of_kind<'a>(&'a self, kind: &'a SpawnKind) -> impl Iterator<Item = &'a SpawnKey> {
+        self.kinds
+            .iter()
+            .filter(move |(_, other)| *other == kind)
+            .map(|(key, _)| key)
+    }
+
+    /// Returns an [`InternedSpawnKey`] for the given [`SpawnKey`].
+    ///
+    /// Interning the same key more than once returns the same handle.
+    /// The key does not need to be registered yet. Registering, replacing, or unregistering it later
+    /// is reflected by the interned handle.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use moonshine_spawn::prelude::*;
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Chicken;
+    ///
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, SpawnPlugin));
+    /// app.add_spawnable("chicken", Chicken);
+    ///
+    /// let world = app.world_mut();
+    /// let chicken = world.resource_mut::<Spawnables>().intern("chicken");
+    /// let entity = world.spawn_key(chicken).id();
+    /// assert!(world.entity(entity).contains::<Chicken>());
+    /// ```
+    pub fn intern(&mut self, key: impl Into<SpawnKey>) -> InternedSpawnKey {
+        let key = key.into();
+        let key = self.lookup_key(&key).into_owned();
+        if let Some(&interned) = self.interned.get(&key) {
+            return interned;
+        }
+        let index = u32::try_from(self.slots.len()).expect("too many interned spawn keys");
+        let interned = InternedSpawnKey(index);
+        self.slots.push((key.clone(), self.get(&key)));
+        self.interned.insert(key, interned);
+        interned
+    }
+
+    /// Returns the [`SpawnKey`] of the given [`InternedSpawnKey`], if it was interned by this registry.
+    pub fn interned_key(&self, key: InternedSpawnKey) -> Option<&SpawnKey> {
+        self.slots.get(key.index()).map(|(key, _)| key)
+    }
+
+    /// Returns the spawnable registered with the given [`InternedSpawnKey`], if any.
+    ///
+    /// Unlike [`Spawnables::get`], this does not hash the key name.
+    pub fn get_interned(&self, key: InternedSpawnKey) -> Option<Arc<dyn Spawnable>> {
+        self.slots
+            .get(key.index())
+            .and_then(|(_, spawnable)| spawnable.clone())
+    }
+
+    fn insert(
+        &mut self,
+        key: SpawnKey,
+        spawnable: Arc<dyn Spawnable>,
+    ) -> Option<Arc<dyn Spawnable>> {
+        let key = self.lookup_key(&key).into_owned();
+        if let Some(interned) = self.interned.get(&key) {
+            self.slots[interned.index()].1 = Some(spawnable.clone());
+        }
+        if key.is_case_insensitive() {
+            self.case_insensitive
+                .insert(key.canonical_name().to_owned());
+        }
+        self.entries.insert(key, spawnable)
+    }
+
+    fn remove(&mut self, key: &SpawnKey) -> Option<Arc<dyn Spawnable>> {
+        let key = self.lookup_key(key);
+        if let Some(interned) = self.interned.get(&*key) {
+            self.slots[interned.index()].1 = None;
+        }
+        let (key, spawnable) = self.entries.remove_entry(&*key)?;
+        if key.is_case_insensitive() {
+            self.case_insensitive.remove(key.canonical_name());
+        }
+        Some(spawnable)
+    }
+
+    /// Returns the key under which the given [`SpawnKey`] is registered.
+    ///
+    /// A key registered as case-insensitive (see [`SpawnKey::new_ci`]) is found using any key with the same name
+    /// in any case, including case-sensitive keys such as those parsed from data files.
+    fn lookup_key<'a>(&self, key: &'a SpawnKey) -> Cow<'a, SpawnKey> {
+        if self.case_insensitive.is_empty() || self.entries.contains_key(key) {
+            return Cow::Borrowed(key);
+        }
+        let name = key.name().to_lowercase();
+        if self.case_insensitive.contains(&name) {
+            // Case-insensitive keys are equal to any other case-insensitive key with the same lowercase name:
+            Cow::Owned(SpawnKey::new_ci(name))
+        } else {
+            Cow::Borrowed(key)
+        }
+    }
+}
+
+/// A compact handle to a [`SpawnKey`], obtained from [`Spawnables::intern`].
+///
+/// Spawning via an interned key avoids hashing or cloning the key name, which is useful when spawning at high frequency.
+/// Entities spawned this way record the interned key in their [`SpawnSource`].
+/// An interned key is only valid for the [`Spawnables`] which created it (or any clone of it).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+pub struct InternedSpawnKey(u32);
+
+impl InternedSpawnKey {
+    /// Returns the index of this key within its [`Spawnables`].
+    pub fn index(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl SpawnableOnce for InternedSpawnKey {
+    fn spawn_once(self, world: &mut World, entity: Entity) {
+        let spawnables = world.resource::<Spawnables>();
+        let Some((key, spawnable)) = spawnables.slots.get(self.index()) else {
+            panic!("interned spawn key is not valid: {self:?}");
+        };
+        let detect_key_cycles = world
+            .get_resource::<SpawnConfig>()
+            .is_some_and(|config| config.detect_key_cycles);
+        match spawnable.clone() {
+            Some(spawnable) if !detect_key_cycles && !spawnable.records_key() => {
+                #[cfg(feature = "trace")]
+                let _span = info_span!("spawn_key", key = %key.name()).entered();
+                spawnable.spawn(world, entity);
+                SpawnKeyRef::Interned(self).spawned(world, entity);
+            }
+            // Missing keys and cycle detection are handled like any other keyed child:
+            spawnable => KeyedChild {
+                key: key.clone(),
+                interned: Some(self),
+                spawnable,
+            }
+            .spawn_once(world, entity),
+        }
+    }
+
+    fn spawn_once_dyn(self: Box<Self>, world: &mut World, entity: Entity) {
+        SpawnableOnce::spawn_once(*self, world, entity);
+    }
+}
+
+/// A reference to a spawnable, either by its [`SpawnKey`] or by an [`InternedSpawnKey`].
+///
+/// Functions which spawn via a key, such as [`SpawnWorld::spawn_key`], accept either form.
+/// Any type which may be converted into a [`SpawnKey`] may also be converted into a [`SpawnKeyRef`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Reflect)]
+pub enum SpawnKeyRef {
+    /// A spawnable referenced by its [`SpawnKey`].
+    Key(SpawnKey),
+    /// A spawnable referenced by an [`InternedSpawnKey`].
+    Interned(InternedSpawnKey),
+}
+
+impl SpawnKeyRef {
+    /// Returns the [`SpawnKey`] of this reference, resolving interned keys using the given [`Spawnables`].
+    ///
+    /// Returns `None` if this is an interned key which was not created by the given registry.
+    pub fn resolve<'a>(&'a self, spawnables: &'a Spawnables) -> Option<&'a SpawnKey> {
+        match self {
+            Self::Key(key) => Some(key),
+            Self::Interned(key) => spawnables.interned_key(*key),
+        }
+    }
+
+    /// Returns an error if this key may not be spawned without parameters, because it is not registered
+    /// or requires parameters (see [`SpawnWorld::try_spawn_key`]).
+    fn check(&self, spawnables: &Spawnables) -> Result<(), SpawnError> {
+        let Some(key) = self.resolve(spawnables) else {
+            panic!("interned spawn key is not valid: {self:?}");
+        };
+        spawnables.try_get(key).map(|_| ())
+    }
+
+    /// Spawns this key into the given [`Entity`] if it passes [`SpawnKeyRef::check`], like any other keyed spawn.
+    fn try_spawn_once(self, world: &mut World, entity: Entity) -> Result<(), SpawnError> {
+        self.check(world.resource::<Spawnables>())?;
+        self.spawn_once(world, entity);
+        Ok(())
+    }
+
+    fn spawned(self, world: &mut World, entity: Entity) {
+        let config = world.get_resource::<SpawnConfig>();
+        let record_spawn_source = config.is_none_or(|config| config.record_spawn_source);
+        #[cfg(feature = "name")]
+        let name_from_key = config.is_some_and(|config| config.name_from_key);
+        let spawnables = world.get_resource::<Spawnables>();
+        let key = spawnables.and_then(|spawnables| self.resolve(spawnables));
+        #[cfg(feature = "name")]
+        let name = key
+            .filter(|_| name_from_key && !world.entity(entity).contains::<Name>())
+            .map(|key| Name::new(key.name().to_owned()));
+        let kind = key
+            .zip(spawnables)
+            .and_then(|(key, spawnables)| spawnables.kind_of(key))
+            .cloned();
+        let child = SpawningChild::contains(world, entity);
+        let mut entity_mut = world.entity_mut(entity);
+        #[cfg(feature = "name")]
+        if let Some(name) = name {
+            entity_mut.insert(name);
+        }
+        // Keyed children are marked along with their source and kind, so the marker adds no archetype move:
+        match (record_spawn_source.then(|| SpawnSource(self.clone())), kind) {
+            (Some(source), Some(kind)) if child => entity_mut.insert((source, kind, SpawnedChild)),
+            (Some(source), Some(kind)) => entity_mut.insert((source, kind)),
+            (Some(source), None) if child => entity_mut.insert((source, SpawnedChild)),
+            (Some(source), None) => entity_mut.insert(source),
+            (None, Some(kind)) if child => entity_mut.insert((kind, SpawnedChild)),
+            (None, Some(kind)) => entity_mut.insert(kind),
+            (None, None) => &mut entity_mut,
+        };
+        #[cfg(feature = "journal")]
+        SpawnJournal::record_key(world, entity, &self);
+        world.trigger_targets(SpawnedByKey { entity, key: self }, entity);
+    }
+}
+
+impl<T: Into<SpawnKey>> From<T> for SpawnKeyRef {
+    fn from(key: T) -> Self {
+        Self::Key(key.into())
+    }
+}
+
+impl From<InternedSpawnKey> for SpawnKeyRef {
+    fn from(key: InternedSpawnKey) -> Self {
+        Self::Interned(key)
+    }
+}
+
+impl SpawnableOnce for SpawnKeyRef {
+    fn spawn_once(self, world: &mut World, entity: Entity) {
+        match self {
+            Self::Key(key) => key.spawn_once(world, entity),
+            Self::Interned(key) => key.spawn_once(world, entity),
+        }
+    }
+
+    fn spawn_once_dyn(self: Box<Self>, world: &mut World, entity: Entity) {
+        SpawnableOnce::spawn_once(*self, world, entity);
+    }
+
+    fn key(&self) -> Option<&SpawnKey> {
+        match self {
+            Self::Key(key) => Some(key),
+            Self::Interned(_) => None,
+        }
+    }
+}
+
+/// Declares a module of [`SpawnKey`] constants.
+///
+/// Each key is named after its identifier, unless a name is given explicitly.
+/// This allows the same constants to be used for both registration and spawning, which avoids typos in key names.
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::prelude::*;
+///
+/// spawn_keys! {
+///     pub mod keys {
+///         CHICKEN,
+///         EGG = "chicken/egg",
+///     }
+/// }
+///
+/// #[derive(Component, Clone)]
+/// struct Chicken;
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, SpawnPlugin));
+/// app.add_spawnable(keys::CHICKEN, Chicken);
+/// app.world_mut().spawn_key(keys::CHICKEN);
+/// assert_eq!(keys::EGG.name(), "chicken/egg");
+/// ```
+#[macro_export]
+macro_rules! spawn_keys {
+    (
+        $(#[$meta:meta])*
+        $vis:vis mod $module:ident {
+            $($(#[$key_meta:meta])* $key:ident $(= $name:literal)?),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis mod $module {
+            $(
+                $(#[$key_meta])*
+                pub const $key: $crate::SpawnKey =
+                    $crate::SpawnKey::from_static($crate::spawn_keys!(@name $key $($name)?));
+            )*
+        }
+    };
+    (@name $key:ident $name:literal) => {
+        $name
+    };
+    (@name $key:ident) => {
+        stringify!($key)
+    };
+}
+
+/// A unique string-based identifier used to spawn a spawnable registered with [`Spawnables`].
+///
+/// Keys created from a `&'static str` do not allocate.
+///
+/// By default, keys are case-sensitive. See [`SpawnKey::new_ci`] for case-insensitive keys.
+///
+/// With the `serde` feature, keys are serialized as their name, both directly and via reflection.
+/// Deserialized keys are always case-sensitive, but still find spawnables registered with a case-insensitive key
+/// (see [`SpawnKey::new_ci`]).
+#[derive(Clone, Reflect)]
+#[reflect(SpawnKey)]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+pub struct SpawnKey(Cow<'static, str>, Option<String>);
+
+/// Type data for [`SpawnKey`], used by editor tooling to choose a key from all registered keys.
+///
+/// This is registered automatically by the [`SpawnPlugin`]. An inspector may fetch it from the type registration
+/// of any reflected value, and if present, show a dropdown of [`ReflectSpawnKey::choices`] instead of a text field.
+/// The chosen key is then written back using [`ReflectSpawnKey::set`].
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy::reflect::PartialReflect;
+/// # use moonshine_spawn::{prelude::*, ReflectSpawnKey};
+///
+/// #[derive(Component, Clone)]
+/// struct Chicken;
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, SpawnPlugin));
+/// app.add_spawnable("chicken", Chicken);
+/// app.add_spawnable("egg", Chicken);
+///
+/// // An inspector would do this for every reflected field it displays:
+/// let mut value = SpawnKey::new("chicken");
+/// let field: &mut dyn PartialReflect = &mut value;
+/// let registry = app.world().resource::<AppTypeRegistry>().read();
+/// let type_id = field.get_represented_type_info().unwrap().type_id();
+/// if let Some(reflect_key) = registry.get_type_data::<ReflectSpawnKey>(type_id) {
+///     let choices = reflect_key.choices(app.world());
+///     assert_eq!(choices, [SpawnKey::new("chicken"), SpawnKey::new("egg")]);
+///     reflect_key.set(field, choices[1].clone());
+/// }
+/// assert_eq!(value, SpawnKey::new("egg"));
+/// ```
+#[derive(Clone)]
+pub struct ReflectSpawnKey(());
+
+impl ReflectSpawnKey {
+    /// Returns all [`SpawnKey`]s registered with the [`Spawnables`] of the given [`World`], sorted by name.
+    pub fn choices(&self, world: &World) -> Vec<SpawnKey> {
+        world
+            .get_resource::<Spawnables>()
+            .map(|spawnables| spawnables.keys_sorted().into_iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the given reflected value as a [`SpawnKey`], if it is one.
+    pub fn get<'a>(&self, value: &'a dyn PartialReflect) -> Option<&'a SpawnKey> {
+        value.try_downcast_ref::<SpawnKey>()
+    }
+
+    /// Replaces the given reflected value with the given [`SpawnKey`], and returns `true` if it is a key.
+    pub fn set(&self, value: &mut dyn PartialReflect, key: SpawnKey) -> bool {
+        let Some(value) = value.try_downcast_mut::<SpawnKey>() else {
+            return false;
+        };
+        *value = key;
+        true
+    }
+
+    /// Returns `true` if the given reflected value is a [`SpawnKey`] registered with the [`Spawnables`] of the given [`World`].
+    ///
+    /// This is useful to highlight invalid keys in an inspector.
+    pub fn is_registered(&self, world: &World, value: &dyn PartialReflect) -> bool {
+        self.get(value).is_some_and(|key| {
+            world
+                .get_resource::<Spawnables>()
+                .is_some_and(|spawnables| spawnables.contains(key))
+        })
+    }
+}
+
+impl FromType<SpawnKey> for ReflectSpawnKey {
+    fn from_type() -> Self {
+        Self(())
+    }
+}
+
+impl SpawnKey {
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self(name.into(), None)
+    }
+
+    /// Creates a new case-insensitive [`SpawnKey`].
+    ///
+    /// A case-insensitive key is compared and hashed using its lowercase name, while [`SpawnKey::name`]
+    /// still returns the original name. It is only equal to other case-insensitive keys, never to a case-sensitive
+    /// key, so that equality does not depend on which key is compared first.
+    ///
+    /// A spawnable registered with a case-insensitive key may be spawned using any key with the same name
+    /// in any case, including keys created from a plain `&str`, such as those read from data files.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use moonshine_spawn::prelude::*;
+    /// assert_eq!(SpawnKey::new_ci("Goblin"), SpawnKey::new_ci("GOBLIN"));
+    /// assert_ne!(SpawnKey::new_ci("Goblin"), SpawnKey::new("goblin"));
+    /// assert_eq!(SpawnKey::new_ci("Goblin").name(), "Goblin");
+    ///
+    /// #[derive(Component, Clone)]
+    /// struct Goblin;
+    ///
+    /// let mut spawnables = Spawnables::default();
+    /// spawnables.register(SpawnKey::new_ci("goblin"), Goblin);
+    /// assert!(spawnables.contains(&"GOBLIN".into()));
+    /// ```
+    pub fn new_ci(name: impl Into<Cow<'static, str>>) -> Self {
+        let name = name.into();
+        let canonical = name.to_lowercase();
+        Self(name, Some(canonical))
+    }
+
+    /// Returns `true` if this key may be registered, i.e. it is not empty or whitespace-only.
+    ///
+    /// This is useful to check keys parsed from external content before registering them.
+    ///
+    /// # Example
+    /// ```
+    /// # use moonshine_spawn::prelude::*;
+    /// assert!(SpawnKey::new("chicken").is_valid());
+    /// assert!(!SpawnKey::new("").is_valid());
+    /// assert!(!SpawnKey::new("  ").is_valid());
+    /// ```
+    pub fn is_valid(&self) -> bool {
+        !self.name().trim().is_empty()
+    }
+
+    /// Returns `true` if this key is case-insensitive.
+    pub fn is_case_insensitive(&self) -> bool {
+        self.1.is_some()
+    }
+
+    fn canonical_name(&self) -> &str {
+        self.1.as_deref().unwrap_or(&self.0)
+    }
+
+    /// Returns `true` if the given key finds a spawnable registered with this key (see [`SpawnKey::new_ci`]).
+    fn finds(&self, other: &SpawnKey) -> bool {
+        self == other
+            || self.is_case_insensitive() && other.name().to_lowercase() == self.canonical_name()
+    }
+
+    /// Creates a new [`SpawnKey`] from a static string without allocation.
+    ///
+    /// This is useful for declaring spawn keys as constants:
+    /// ```
+    /// # use moonshine_spawn::prelude::*;
+    /// const CHICKEN: SpawnKey = SpawnKey::from_static("chicken");
+    /// ```
+    pub const fn from_static(name: &'static str) -> Self {
+        Self(Cow::Borrowed(name), None)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the namespace of this key, if any.
+    ///
+    /// The namespace is everything before the last `/` in the key name.
+    ///
+    /// # Example
+    /// ```
+    /// # use moonshine_spawn::prelude::*;
+    /// assert_eq!(SpawnKey::new("enemies/goblin").namespace(), Some("enemies"));
+    /// assert_eq!(SpawnKey::new("world/enemies/goblin").namespace(), Some("world/enemies"));
+    /// assert_eq!(SpawnKey::new("goblin").namespace(), None);
+    /// ```
+    pub fn namespace(&self) -> Option<&str> {
+        self.name().rsplit_once('/').map(|(namespace, _)| namespace)
+    }
+
+    /// Creates a new [`SpawnKey`] from the given file path.
+    ///
+    /// The key is the path without any extensions, using `/` as the separator on all platforms.
+    /// Empty and `.` segments are removed. This is the same key used for spawnable assets.
+    ///
+    /// # Example
+    /// ```
+    /// # use std::path::Path;
+    /// # use moonshine_spawn::prelude::*;
+    /// let key = SpawnKey::from_path(Path::new("prefabs\\enemies/goblin.spawn.ron"));
+    /// assert_eq!(key.name(), "prefabs/enemies/goblin");
+    /// ```
+    pub fn from_path(path: &Path) -> Self {
+        let path = path.to_string_lossy();
+        let mut segments: Vec<&str> = path
+            .split(['/', '\\'])
+            .filter(|segment| !segment.is_empty() && *segment != ".")
+            .collect();
+        if let Some(last) = segments.last_mut() {
+            // Leading dots mark hidden files, not extensions:
+            if let Some(index) = last
+                .char_indices()
+                .skip(1)
+                .find(|&(_, c)| c == '.')
+                .map(|(i, _)| i)
+            {
+                *last = &last[..index];
+            }
+        }
+        Self::new(segments.join("/"))
+    }
+}
+
+impl PartialEq for SpawnKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.is_case_insensitive() == other.is_case_insensitive()
+            && self.canonical_name() == other.canonical_name()
+    }
+}
+
+impl Eq for SpawnKey {}
+
+impl Hash for SpawnKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.is_case_insensitive().hash(state);
+        self.canonical_name().hash(state);
+    }
+}
+
+impl Debug for SpawnKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        f.debug_tuple("SpawnKey").field(&self.name()).finish()
+    }
+}
+
+impl From<String> for SpawnKey {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<&str> for SpawnKey {
+    fn from(name: &str) -> Self {
+        Self::new(name.to_owned())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SpawnKey {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SpawnKey {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::new)
+    }
+}
+
+impl From<&String> for SpawnKey {
+    fn from(name: &String) -> Self {
+        Self::new(name.clone())
+    }
+}
+
+/// Only [`Cow::Owned`] names are moved into the key without allocation. Borrowed names are copied,
+/// even if `'static`; use [`SpawnKey::new`] or [`SpawnKey::from_static`] to keep a `'static` name without allocation.
+impl From<Cow<'_, str>> for SpawnKey {
+    fn from(name: Cow<'_, str>) -> Self {
+        Self::new(name.into_owned())
+    }
+}
+
+/// Trait used to define strongly typed identifiers for spawnables, typically as an enum.
+///
+/// Any type which implements this trait may be converted into a [`SpawnKey`],
+/// and therefore used anywhere a spawn key is expected.
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::prelude::*;
+///
+/// #[derive(Clone, Copy)]
+/// enum Prefab {
+///     Chicken,
+///     Egg,
+/// }
+///
+/// impl SpawnId for Prefab {
+///     fn key_name(&self) -> &'static str {
+///         match self {
+///             Prefab::Chicken => "chicken",
+///             Prefab::Egg => "egg",
+///         }
+///     }
+/// }
+///
+/// #[derive(Component, Clone)]
+/// struct Chicken;
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, SpawnPlugin));
+/// app.add_spawnable(Prefab::Chicken, Chicken);
+/// app.world_mut().spawn_key(Prefab::Chicken);
+/// ```
+pub trait SpawnId {
+    /// Returns the unique name of the [`SpawnKey`] represented by this identifier.
+    ///
+    /// This name must be stable, as it is used to register and resolve the spawnable.
+    fn key_name(&self) -> &'static str;
+}
+
+impl<T: SpawnId> From<T> for SpawnKey {
+    fn from(id: T) -> Self {
+        Self::from_static(id.key_name())
+    }
+}
+
+/// An error which may occur while spawning a spawnable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpawnError {
+    /// The given [`SpawnKey`] is not registered with [`Spawnables`].
+    InvalidKey(SpawnKey),
+    /// The given [`SpawnKey`] does not accept parameters of the given type.
+    InvalidParams(SpawnKey, &'static str),
+    /// The given [`SpawnKey`] requires parameters, but was spawned without any (see [`SpawnWorld::spawn_key_with_params`]).
+    MissingParams(SpawnKey),
+    /// The spawnable registered with the given [`SpawnKey`] requires a resource which does not exist.
+    MissingResource(SpawnKey, &'static str),
+    /// A [`WeightedSpawnable`] has no keys with a positive weight to choose from.
+    NoWeightedKey,
+    /// The spawnable registered with the given [`SpawnKey`] did not insert the given component
+    /// (see [`SpawnWorld::spawn_key_as`]).
+    MissingComponent(SpawnKey, &'static str),
+}
+
+impl Display for SpawnError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        match self {
+            Self::InvalidKey(key) => write!(f, "invalid spawn key: {key:?}"),
+            Self::InvalidParams(key, params) => {
+                write!(
+                    f,
+                    "spawn key {key:?} does not accept parameters of type {params}"
+                )
+            }
+            Self::MissingParams(key) => write!(f, "spawn key {key:?} requires parameters"),
+            Self::MissingResource(key, resource) => {
+                write!(f, "spawn key {key:?} requires missing resource {resource}")
+            }
+            Self::NoWeightedKey => write!(f, "weighted spawnable has no keys with positive weight"),
+            Self::MissingComponent(key, component) => {
+                write!(f, "spawn key {key:?} does not spawn a `{component}`")
+            }
+        }
+    }
+}
+
+impl Error for SpawnError {}
+
+/// An error which may occur while registering a spawnable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpawnRegisterError {
+    /// The given [`SpawnKey`] is already registered with [`Spawnables`].
+    DuplicateKey(SpawnKey),
+    /// The given [`SpawnKey`] is empty or contains only whitespace (see [`SpawnKey::is_valid`]).
+    InvalidKey(SpawnKey),
+    /// The given [`SpawnKey`] is not registered with [`Spawnables`] (see [`Spawnables::register_alias`]).
+    MissingKey(SpawnKey),
+}
+
+impl Display for SpawnRegisterError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        match self {
+            Self::DuplicateKey(key) => write!(f, "spawn key must be unique: {key:?}"),
+            Self::InvalidKey(key) => write!(f, "spawn key must not be empty: {key:?}"),
+            Self::MissingKey(key) => write!(f, "spawn key is not registered: {key:?}"),
+        }
+    }
+}
+
+impl Error for SpawnRegisterError {}
+
+/// An [`Event`] sent when a deferred spawn fails.
+///
+/// See [`SpawnCommands::try_spawn_key`] for details.
+#[derive(Event, Debug, Clone)]
+pub struct SpawnFailed {
+    /// The [`Entity`] which was reserved for the spawn.
+    ///
+    /// If it was spawned via [`SpawnCommands::try_spawn_key`] or as a child, this entity is despawned by the time
+    /// this event is sent.
+    pub entity: Entity,
+    /// The cause of the failure.
+    pub error: SpawnError,
+}
+
+/// A [`Component`] which stores the [`SpawnKeyRef`] used to spawn its [`Entity`].
+///
+/// This component is inserted automatically into any entity spawned via a [`SpawnKey`] or an [`InternedSpawnKey`],
+/// unless disabled via [`SpawnConfig::record_spawn_source`]. Interned keys are recorded as-is, and may be resolved
+/// using [`SpawnSource::resolve`].
+///
+/// This is useful to find all instances of a spawnable, or to save an entity by its key rather than its components.
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::prelude::*;
+///
+/// fn find_chickens(query: Query<(Entity, &SpawnSource)>, spawnables: Res<Spawnables>) {
+///     for (entity, source) in query.iter() {
+///         if source.resolve(&spawnables).is_some_and(|key| key.name() == "chicken") {
+///             // ...
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct SpawnSource(pub SpawnKeyRef);
+
+impl SpawnSource {
+    /// Returns the [`SpawnKeyRef`] used to spawn this entity.
+    pub fn key(&self) -> &SpawnKeyRef {
+        &self.0
+    }
+
+    /// Returns the [`SpawnKey`] used to spawn this entity, resolving interned keys using the given [`Spawnables`].
+    ///
+    /// See [`SpawnKeyRef::resolve`] for details.
+    pub fn resolve<'a>(&'a self, spawnables: &'a Spawnables) -> Option<&'a SpawnKey> {
+        self.0.resolve(spawnables)
+    }
+}
+
+/// A marker [`Component`] for any root [`Entity`] spawned via [`SpawnCommands`] or [`SpawnWorld`].
+///
+/// This is only inserted if enabled via [`SpawnConfig::mark_spawned_entities`].
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::prelude::*;
+///
+/// fn find_prefabs(query: Query<Entity, With<SpawnedEntity>>) {
+///     for entity in query.iter() {
+///         // ...
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct SpawnedEntity;
+
+/// A [`Component`] which categorizes an [`Entity`] spawned via a [`SpawnKey`], such as `"ui"` or `"effects"`.
+///
+/// Kinds are assigned to keys using [`Spawnables::set_kind`], and inserted into every entity spawned via the key.
+#[derive(Component, Clone, Debug, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component)]
+pub struct SpawnKind(Cow<'static, str>);
+
+impl SpawnKind {
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self(name.into())
+    }
+
+    /// Returns the name of this kind.
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&'static str> for SpawnKind {
+    fn from(name: &'static str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<String> for SpawnKind {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
+
+/// A set of [`Commands`] for an [`Entity`] spawned with [`SpawnCommands::spawn_key_unique`].
+///
+/// The entity is either the one already spawned with the same identity, or a newly spawned one.
+/// Since this is only known once the spawn command is applied, any commands queued here are applied
+/// to whichever entity the identity resolves to.
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::prelude::*;
+///
+/// #[derive(Component, Clone)]
+/// struct Player;
+///
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// fn on_player_joined(mut commands: Commands) {
+///     // Even if this runs twice for the same player, only one entity is spawned:
+///     commands.spawn_key_unique(7_u64, "player").insert(Health(100));
+/// }
+/// ```
+pub struct SpawnUniqueCommands<'a> {
+    commands: Commands<'a, 'a>,
+    slot: SpawnSlot,
+}
+
+impl SpawnUniqueCommands<'_> {
+    /// Returns a [`SpawnSlot`] which holds the resolved [`Entity`] once the spawn command is applied.
+    pub fn slot(&self) -> SpawnSlot {
+        self.slot.clone()
+    }
+
+    /// Inserts the given [`Bundle`] into the resolved [`Entity`].
+    pub fn insert(&mut self, bundle: impl Bundle) -> &mut Self {
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.insert(bundle);
+        })
+    }
+
+    /// Queues a command to be applied to the resolved [`Entity`].
+    ///
+    /// If the entity is despawned before this command is applied, it does nothing.
+    pub fn queue(&mut self, f: impl FnOnce(EntityWorldMut) + Send + 'static) -> &mut Self {
+        let slot = self.slot.clone();
+        self.commands.queue(move |world: &mut World| {
+            if let Some(entity) = slot
+                .get()
+                .and_then(|entity| world.get_entity_mut(entity).ok())
+            {
+                f(entity);
+            }
+        });
+        self
+    }
+}
+
+/// A [`Resource`] which maps identities of type `I` to entities spawned with [`SpawnWorld::spawn_key_unique`].
+///
+/// This resource is inserted automatically on first use.
+/// Entries are not removed when their entity is despawned, but despawned entities are ignored.
+#[derive(Resource)]
+pub struct SpawnIdentities<I>(HashMap<I, Entity>);
+
+impl<I: Hash + Eq> SpawnIdentities<I> {
+    /// Returns the [`Entity`] spawned with the given identity, if any.
+    ///
+    /// Note that the entity may have been despawned since.
+    pub fn get(&self, identity: &I) -> Option<Entity> {
+        self.0.get(identity).copied()
+    }
+
+    /// Removes the given identity, so that it may be spawned again, and returns its [`Entity`], if any.
+    pub fn remove(&mut self, identity: &I) -> Option<Entity> {
+        self.0.remove(identity)
+    }
+}
+
+impl<I> Default for SpawnIdentities<I> {
+    fn default() -> Self {
+        Self(HashMap::default())
+    }
+}
+
+fn spawned_identity<I: Hash + Eq + Send + Sync + 'static>(
+    world: &World,
+    identity: &I,
+) -> Option<Entity> {
+    world
+        .get_resource::<SpawnIdentities<I>>()
+        .and_then(|identities| identities.get(identity))
+        .filter(|&entity| world.get_entity(entity).is_ok())
+}
+
+/// An [`Event`] triggered when a child is spawned from a [`SpawnChildren`] component.
+///
+/// This event is triggered immediately after each child is spawned, for all children within a hierarchy,
+/// including grandchildren. It targets the spawned child, so it may be observed either globally or on the child itself.
+/// Note that children of the child are not spawned yet at this point.
+///
+/// This is useful to insert runtime-only components into every spawned child.
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Chicken;
+///
+/// #[derive(Component)]
+/// struct Selectable;
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, SpawnPlugin));
+/// app.add_observer(|trigger: Trigger<ChildSpawned>, mut commands: Commands| {
+///     commands.entity(trigger.event().child).insert(Selectable);
+/// });
+/// let world = app.world_mut();
+/// let coop = world.spawn_once_with(spawn_children(|coop| {
+///     coop.spawn(Chicken);
+/// })).id();
+/// world.flush();
+/// let chicken = world.get::<Children>(coop).unwrap()[0];
+/// assert!(world.entity(chicken).contains::<Selectable>());
+/// ```
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ChildSpawned {
+    /// The parent [`Entity`] of the spawned child.
+    pub parent: Entity,
+    /// The spawned child [`Entity`].
+    pub child: Entity,
+}
+
+/// An [`Event`] triggered when an [`Entity`] is spawned via a [`SpawnKey`].
+///
+/// This event is triggered immediately after the bundle of the registered spawnable is inserted,
+/// both for entities spawned directly and for children spawned via [`SpawnChildBuilder::spawn_key`].
+/// It targets the spawned entity, so it may be observed either globally or on the entity itself.
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::prelude::*;
+///
+/// #[derive(Component, Clone)]
+/// struct Foo;
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, SpawnPlugin));
+/// app.add_spawnable("FOO", Foo);
+/// app.add_observer(|trigger: Trigger<SpawnedByKey>| {
+///     assert_eq!(trigger.event().key, SpawnKey::new("FOO").into());
+/// });
+/// app.world_mut().spawn_key("FOO");
+/// ```
+#[derive(Event, Debug, Clone)]
+pub struct SpawnedByKey {
+    /// The spawned [`Entity`].
+    pub entity: Entity,
+    /// The [`SpawnKey`] or [`InternedSpawnKey`] used to spawn the entity.
+    pub key: SpawnKeyRef,
+}
+
+/// An [`Entity`] spawned via a [`SpawnKey`] which is guaranteed to have a [`Component`] of type `T`.
+///
+/// See [`SpawnWorld::spawn_key_as`] and [`SpawnCommands::spawn_key_as`].
+///
+/// This type is only available with the `kind` feature.
+#[cfg(feature = "kind")]
+pub struct SpawnInstance<T: Component>(Entity, PhantomData<T>);
+
+#[cfg(feature = "kind")]
+impl<T: Component> SpawnInstance<T> {
+    fn new(entity: Entity) -> Self {
+        Self(entity, PhantomData)
+    }
+
+    /// Returns the spawned [`Entity`].
+    pub fn entity(&self) -> Entity {
+        self.0
+    }
+}
+
+#[cfg(feature = "kind")]
+impl<T: Component> Clone for SpawnInstance<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+#[cfg(feature = "kind")]
+impl<T: Component> Copy for SpawnInstance<T> {}
+
+#[cfg(feature = "kind")]
+impl<T: Component> PartialEq for SpawnInstance<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+#[cfg(feature = "kind")]
+impl<T: Component> Eq for SpawnInstance<T> {}
+
+#[cfg(feature = "kind")]
+impl<T: Component> Hash for SpawnInstance<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+#[cfg(feature = "kind")]
+impl<T: Component> Debug for SpawnInstance<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
+        write!(f, "SpawnInstance<{}>({:?})", type_name::<T>(), self.0)
+    }
+}
+
+#[cfg(feature = "kind")]
+impl<T: Component> From<SpawnInstance<T>> for Entity {
+    fn from(instance: SpawnInstance<T>) -> Self {
+        instance.0
+    }
+}
+
+#[cfg(feature = "kind")]
+fn check_instance<T: Component>(
+    world: &World,
+    entity: Entity,
+    key: &SpawnKey,
+) -> Result<SpawnInstance<T>, SpawnError> {
+    if !world
+        .get_entity(entity)
+        .is_ok_and(|entity| entity.contains::<T>())
+    {
+        return Err(SpawnError::MissingComponent(key.clone(), type_name::<T>()));
+    }
+    Ok(SpawnInstance::new(entity))
+}
+
+/// Trait used to attach children to an [`Entity`] using a [`Bundle`].
+///
+/// Children may themselves have children, nested to any depth.
+/// Each level is spawned once its parent is spawned.
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Foo;
+///
+/// #[derive(Component)]
+/// struct Bar;
+///
+/// #[derive(Component)]
+/// struct Baz;
+///
+/// let mut world = World::default();
+/// world.spawn(Foo.with_children(|foo| {
+///    foo.spawn(Bar.with_children(|bar| {
+///        bar.spawn(Baz);
+///    }));
+/// }));
+/// ```
+pub trait WithChildren: Bundle + Sized {
+    fn with_children(self, f: impl FnOnce(&mut SpawnChildBuilder)) -> (Self, SpawnChildren);
+}
+
+impl<T: Bundle> WithChildren for T {
+    fn with_children(self, f: impl FnOnce(&mut SpawnChildBuilder)) -> (Self, SpawnChildren) {
+        let mut children = SpawnChildren::new();
+        let mut builder = SpawnChildBuilder(&mut children);
+        f(&mut builder);
+        (self, children)
+    }
+}
+
+/// A [`Component`] used to compose multiple bundles into a single spawnable at runtime.
+///
+/// Bundles are inserted in the order they are added, as soon as this component is spawned.
+/// This means later bundles replace any components inserted by earlier bundles.
+///
+/// This component is a [`Bundle`] itself, and therefore implements [`SpawnOnce`]. It may also be included
+/// in the output of any other spawnable. It is removed once all of its bundles are inserted.
+///
+/// Note that the bundles are only inserted if this component is spawned as a spawnable.
+/// Inserting it directly into an entity (i.e. `world.spawn(composite)`) does nothing.
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Chicken;
+///
+/// #[derive(Component)]
+/// struct Angry;
+///
+/// let angry = true;
+/// let mut world = World::default();
+/// let chicken = world
+///     .spawn_once_with(
+///         SpawnComposite::new()
+///             .with((Chicken, Name::new("Chicken")))
+///             .with_if(angry, Angry),
+///     )
+///     .id();
+/// assert!(world.entity(chicken).contains::<Angry>());
+/// ```
+#[derive(Component, Default)]
+#[component(storage = "SparseSet")]
+#[allow(clippy::type_complexity)]
+pub struct SpawnComposite(Vec<Box<dyn FnOnce(&mut EntityWorldMut) + Send + Sync>>);
+
+impl SpawnComposite {
+    /// Creates a new empty [`SpawnComposite`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the given [`Bundle`] to this composite.
+    pub fn with(mut self, bundle: impl Bundle) -> Self {
+        self.add(bundle);
+        self
+    }
+
+    /// Adds the given [`Bundle`] to this composite only if the given condition is `true`.
+    pub fn with_if(self, condition: bool, bundle: impl Bundle) -> Self {
+        if condition {
+            self.with(bundle)
+        } else {
+            self
+        }
+    }
+
+    /// Adds the given [`Bundle`] to this composite.
+    pub fn add(&mut self, bundle: impl Bundle) -> &mut Self {
+        self.0.push(Box::new(move |entity| {
+            entity.insert(bundle);
+        }));
+        self
+    }
+
+    /// Returns the number of bundles in this composite.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if this composite has no bundles.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Trait used to attach a [`SpawnFinalizer`] to any [`Bundle`].
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Gun;
+///
+/// #[derive(Component)]
+/// struct Muzzle;
+///
+/// #[derive(Component)]
+/// struct MuzzleRef(Entity);
+///
+/// let mut world = World::default();
+/// let gun = world
+///     .spawn_once_with(
+///         Gun.with_children(|gun| {
+///             gun.spawn_named("muzzle", Muzzle);
+///         })
+///         .with_finalizer(|world, gun| {
+///             let muzzle = find_child_by_spawn_name(world, gun, "muzzle").unwrap();
+///             world.entity_mut(gun).insert(MuzzleRef(muzzle));
+///         }),
+///     )
+///     .id();
+/// assert!(world.entity(gun).contains::<MuzzleRef>());
+/// ```
+pub trait WithFinalizer: Bundle + Sized {
+    fn with_finalizer(
+        self,
+        f: impl 'static + Send + Sync + FnOnce(&mut World, Entity),
+    ) -> (Self, SpawnFinalizer);
+
+    /// Inserts a [`Bundle`] computed once the [`Entity`] and all of its [`SpawnChildren`] are spawned.
+    ///
+    /// This is useful for components which depend on children, such as a layout which sums the sizes of its children.
+    /// This is a shorthand for a [`SpawnFinalizer`] which inserts the returned bundle.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use moonshine_spawn::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Chicken;
+    ///
+    /// #[derive(Component)]
+    /// struct Chick;
+    ///
+    /// #[derive(Component)]
+    /// struct ChickCount(usize);
+    ///
+    /// let mut world = World::new();
+    /// let chicken = world
+    ///     .spawn_once_with(
+    ///         Chicken
+    ///             .with_children(|chicken| {
+    ///                 chicken.spawn(Chick);
+    ///                 chicken.spawn(Chick);
+    ///             })
+    ///             .with_computed(|world, chicken| {
+    ///                 ChickCount(world.get::<Children>(chicken).map_or(0, |children| children.len()))
+    ///             }),
+    ///     )
+    ///     .id();
+    /// assert_eq!(world.get::<ChickCount>(chicken).unwrap().0, 2);
+    /// ```
+    fn with_computed<B: Bundle>(
+        self,
+        f: impl 'static + Send + Sync + FnOnce(&World, Entity) -> B,
+    ) -> (Self, SpawnFinalizer) {
+        self.with_finalizer(move |world, entity| {
+            let bundle = f(world, entity);
+            world.entity_mut(entity).insert(bundle);
+        })
+    }
+}
+
+impl<T: Bundle> WithFinalizer for T {
+    fn with_finalizer(
+        self,
+        f: impl 'static + Send + Sync + FnOnce(&mut World, Entity),
+    ) -> (Self, SpawnFinalizer) {
+        (self, SpawnFinalizer(Box::new(f)))
+    }
+}
+
+/// A [`Component`] which stores a function to invoke once its [`Entity`] and all of its [`SpawnChildren`] are spawned.
+///
+/// This is useful to link entities within a spawned hierarchy, such as siblings which reference each other.
+///
+/// A finalizer runs after its entire hierarchy is spawned, including all nested children.
+/// Finalizers of descendants are invoked before the finalizers of their ancestors.
+/// Like [`SpawnChildren`], if an entity is spawned using [`Commands`], its finalizer is invoked during the next spawn system.
+///
+/// See [`WithFinalizer`] for usage.
+#[derive(Component)]
+#[component(storage = "SparseSet")]
+#[allow(clippy::type_complexity)]
+pub struct SpawnFinalizer(Box<dyn FnOnce(&mut World, Entity) + Send + Sync>);
+
+impl SpawnFinalizer {
+    fn invoke(world: &mut World, entity: Entity) {
+        let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+            return;
+        };
+        if let Some(SpawnFinalizer(finalize)) = entity_mut.take::<SpawnFinalizer>() {
+            finalize(world, entity);
+        }
+    }
+}
+
+/// A [`Component`] which stores a list of spawnables to spawn as children of its [`Entity`].
+///
+/// Children are spawned in the same order as they are added, and appear in that order in the [`Children`]
+/// of their parent, after any existing children. See [`spawn_children_rev`] to spawn children in reverse order.
+///
+/// Children spawned with a [`SpawnKey`] are resolved when this component is inserted.
+/// Registering, replacing, or unregistering that key afterwards does not affect children which are already queued.
+/// If a key is not registered at that time, it is looked up again when the child is spawned.
+///
+/// Each child is added to its parent before it is spawned.
+/// This means a child spawnable may access its parent via the `Parent` component of its own entity:
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Owner(Entity);
+///
+/// struct Weapon;
+///
+/// impl SpawnOnce for Weapon {
+///     type Output = Owner;
+///
+///     fn spawn_once(self, world: &World, entity: Entity) -> Self::Output {
+///         Owner(world.get::<Parent>(entity).unwrap().get())
+///     }
+/// }
+/// ```
+#[derive(Component)]
+#[component(storage = "SparseSet", on_insert = on_insert_spawn_children)]
+pub struct SpawnChildren(Vec<Box<dyn SpawnableOnce>>);
+
+impl SpawnChildren {
+    fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Creates an empty [`SpawnChildren`] with space for at least `capacity` children.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity))
+    }
+
+    /// Returns the number of children which will be spawned.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if no children will be spawned.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Adds a child to be spawned after all existing children.
+    pub fn push(&mut self, spawnable: impl SpawnOnce) {
+        self.add_child(spawnable);
+    }
+
+    /// Appends all children of another [`SpawnChildren`] after the existing children, preserving their order.
+    ///
+    /// This is useful to assemble children in stages, such as when a function returns a partial list of children.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use moonshine_spawn::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Wing;
+    ///
+    /// #[derive(Component)]
+    /// struct Head;
+    ///
+    /// let mut children = spawn_children(|chicken| {
+    ///     chicken.spawn(Wing);
+    ///     chicken.spawn(Wing);
+    /// });
+    /// children.extend(spawn_children(|chicken| {
+    ///     chicken.spawn(Head);
+    /// }));
+    /// assert_eq!(children.len(), 3);
+    /// ```
+    pub fn extend(&mut self, other: SpawnChildren) {
+        self.0.extend(other.0);
+    }
+
+    /// Returns a [`SpawnNode`] for each child which will be spawned, in order, without spawning them.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use moonshine_spawn::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Chicken;
+    ///
+    /// let children = spawn_children(|chicken| {
+    ///     chicken.spawn_key("chicken/head");
+    ///     chicken.spawn(Chicken);
+    /// });
+    /// let nodes = children.describe();
+    /// assert_eq!(nodes[0].key, Some(SpawnKey::new("chicken/head")));
+    /// assert!(nodes[1].is_opaque());
+    /// ```
+    #[cfg(feature = "describe")]
+    pub fn describe(&self) -> Vec<SpawnNode> {
+        self.0.iter().map(|child| child.describe()).collect()
+    }
+
+    fn add_child(&mut self, spawnable: impl SpawnableOnce) {
+        self.0.push(Box::new(spawnable));
+    }
+
+    fn add_child_with_key(&mut self, key: SpawnKey) {
+        self.0.push(Box::new(KeyedChild::new(key)));
+    }
+
+    fn invoke(world: &mut World, entity: Entity, mut child_spawned: impl FnMut(Entity)) {
+        let Ok(mut entity_mut) = world.get_entity_mut(entity) else {
+            debug!("skipping spawn children of {entity:?}: entity no longer exists");
+            return;
+        };
+        if let Some(children) = entity_mut.take::<SpawnChildren>() {
+            for spawnable in children.0 {
+                let child = world.spawn_empty().id();
+                // The parent may be despawned while its children are spawned (i.e. by a `ChildSpawned` observer).
+                // Spawning the child flushes the world, so any such despawn is already applied:
+                let Ok(mut parent) = world.get_entity_mut(entity) else {
+                    debug!("skipping remaining spawn children of {entity:?}: entity was despawned");
+                    world.despawn(child);
+                    return;
+                };
+                // Add the child before spawning it so that its spawnable may access its parent (see `SpawnContext`):
+                parent.add_child(child);
+                let spawning = SpawningChild::begin(world, child);
+                #[cfg(feature = "journal")]
+                let previous = SpawnJournal::begin_child(world, child);
+                spawnable.spawn_once_dyn(world, child);
+                #[cfg(feature = "journal")]
+                SpawnJournal::end_child(world, previous);
+                SpawningChild::end(world, spawning);
+                // The child may have been despawned if its spawn was cancelled (see `TrySpawnOnce`):
+                if world.get_entity(child).is_ok() {
+                    world.trigger_targets(
+                        ChildSpawned {
+                            parent: entity,
+                            child,
+                        },
+                        child,
+                    );
+                    child_spawned(child);
+                }
+            }
+        }
+    }
+}
+
+/// A description of a child in a [`SpawnChildren`] component, used to inspect it before it is spawned.
+///
+/// See [`SpawnChildren::describe`] for details.
+///
+/// Only children spawned via a [`SpawnKey`] may be described. Any other child is opaque,
+/// since its components and children are not known until it is spawned.
+#[cfg(feature = "describe")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpawnNode {
+    /// The [`SpawnKey`] used to spawn this child, if any.
+    pub key: Option<SpawnKey>,
+    /// Any additional children which are known before spawn (see [`SpawnChildBuilder::spawn_key_with_children`]).
+    pub children: Vec<SpawnNode>,
+}
+
+#[cfg(feature = "describe")]
+impl SpawnNode {
+    /// Returns `true` if nothing is known about this child before it is spawned.
+    pub fn is_opaque(&self) -> bool {
+        self.key.is_none() && self.children.is_empty()
+    }
+}
+
+/// An ergonomic function used to create a [`SpawnChildren`] component.
+#[must_use]
+pub fn spawn_children(f: impl FnOnce(&mut SpawnChildBuilder)) -> SpawnChildren {
+    let mut children = SpawnChildren::new();
+    f(&mut SpawnChildBuilder(&mut children));
+    children
+}
+
+/// Similar to [`spawn_children`], except children are spawned in the reverse order in which they are added.
+///
+/// This is useful for cases where the last added child must appear first, such as stacking UI from bottom to top.
+#[must_use]
+pub fn spawn_children_rev(f: impl FnOnce(&mut SpawnChildBuilder)) -> SpawnChildren {
+    let mut children = spawn_children(f);
+    children.0.reverse();
+    children
+}
+
+impl Default for SpawnChildren {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct SpawnChildBuilder<'a>(&'a mut SpawnChildren);
+
+impl SpawnChildBuilder<'_> {
+    /// Reserves space for at least `additional` more children.
+    ///
+    /// This is useful when spawning many children in a loop, such as tiles of a grid.
+    pub fn reserve(&mut self, additional: usize) -> &mut Self {
+        self.0 .0.reserve(additional);
+        self
+    }
+
+    pub fn spawn(&mut self, spawnable: impl SpawnOnce) -> &mut Self {
+        self.0.add_child(spawnable);
+        self
+    }
+
+    /// Spawns a child using a [`TrySpawnOnce`] spawnable.
+    ///
+    /// If the spawn is cancelled, the child is despawned and removed from its parent.
+    pub fn try_spawn(&mut self, spawnable: impl TrySpawnOnce) -> &mut Self {
+        self.0.add_child(TrySpawnable(spawnable));
+        self
+    }
+
+    /// Spawns a child using a [`Spawn`] spawnable, similar to [`SpawnCommands::spawn_with`].
+    pub fn spawn_with(&mut self, spawnable: impl Spawn) -> &mut Self {
+        self.0.add_child(SpawnWith(spawnable));
+        self
+    }
+
+    pub fn spawn_key(&mut self, key: impl Into<SpawnKeyRef>) -> &mut Self {
+        match key.into() {
+            SpawnKeyRef::Key(key) => self.0.add_child_with_key(key),
+            SpawnKeyRef::Interned(key) => self.0.add_child(key),
+        }
+        self
+    }
+
+    /// Spawns a child via each given [`SpawnKey`], in iteration order.
+    pub fn spawn_keys(&mut self, keys: impl IntoIterator<Item = impl Into<SpawnKey>>) -> &mut Self {
+        for key in keys {
+            self.spawn_key(key);
+        }
+        self
+    }
+
+    /// Spawns a child for each given spawnable, in iteration order.
+    pub fn spawn_many(
+        &mut self,
+        spawnables: impl IntoIterator<Item = impl SpawnOnce>,
+    ) -> &mut Self {
+        for spawnable in spawnables {
+            self.spawn(spawnable);
+        }
+        self
+    }
+
+    /// Spawns a child via a [`SpawnKey`] and inserts the given [`Bundle`] into it.
+    ///
+    /// See [`SpawnWorld::spawn_key_with`] for details.
+    pub fn spawn_key_with(&mut self, key: impl Into<SpawnKey>, bundle: impl Bundle) -> &mut Self {
+        self.0.add_child(SpawnKeyWith(KeyedChild::new(key), bundle));
+        self
+    }
+
+    /// Spawns a child via a [`SpawnKey`] with some additional children.
+    ///
+    /// See [`SpawnCommands::spawn_key_with_children`] for details.
+    pub fn spawn_key_with_children(
+        &mut self,
+        key: impl Into<SpawnKey>,
+        f: impl FnOnce(&mut SpawnChildBuilder),
+    ) -> &mut Self {
+        self.0.add_child(SpawnKeyWithChildren(
+            KeyedChild::new(key),
+            spawn_children(f),
+        ));
+        self
+    }
+
+    /// Spawns a child with a [`SpawnName`], which may be used to find it later.
+    ///
+    /// See [`find_child_by_spawn_name`] for details.
+    pub fn spawn_named(&mut self, name: impl Into<String>, spawnable: impl SpawnOnce) -> &mut Self {
+        self.0
+            .add_child(SpawnNamed(SpawnName(name.into()), spawnable));
+        self
+    }
+
+    /// Spawns a child only if the given condition is `true`.
+    pub fn spawn_if(&mut self, condition: bool, spawnable: impl SpawnOnce) -> &mut Self {
+        if condition {
+            self.spawn(spawnable);
+        }
+        self
+    }
+
+    /// Spawns an entity along with the children of this builder, but without a parent.
+    ///
+    /// The entity is spawned at the same time as any other child, including its own children and finalizer,
+    /// but it is not added as a child of the entity which owns the [`SpawnChildren`].
+    /// This is useful for spawnables which produce side entities in world space, such as effects.
+    ///
+    /// Note that because the entity has no parent, its spawnable may not access the owner via [`SpawnContext`].
+    pub fn spawn_detached(&mut self, spawnable: impl SpawnOnce) -> &mut Self {
+        self.0.add_child(SpawnDetached(spawnable));
+        self
+    }
+
+    /// Spawns a child only if the given spawnable is `Some`.
+    ///
+    /// If the spawnable is `None`, no child entity is spawned at all.
+    pub fn spawn_option(&mut self, spawnable: Option<impl SpawnOnce>) -> &mut Self {
+        if let Some(spawnable) = spawnable {
+            self.spawn(spawnable);
+        }
+        self
+    }
+
+    /// Spawns a child via a [`SpawnKey`] only if the given condition is `true`.
+    pub fn spawn_key_if(&mut self, condition: bool, key: impl Into<SpawnKey>) -> &mut Self {
+        if condition {
+            self.spawn_key(key);
+        }
+        self
+    }
+
+    /// Spawns a child and returns a [`SpawnSlot`] which captures its [`Entity`] once it is spawned.
+    ///
+    /// This is useful to reference children from a [`SpawnFinalizer`], such as to link siblings together.
+    /// Note that the slot is empty until the child is actually spawned.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use moonshine_spawn::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Owner;
+    ///
+    /// #[derive(Component)]
+    /// struct HealthBar(Entity);
+    ///
+    /// let mut world = World::default();
+    /// let mut bar = None;
+    /// let owner = world
+    ///     .spawn_once_with(
+    ///         Owner
+    ///             .with_children(|owner| {
+    ///                 bar = Some(owner.spawn_capture(Name::new("Health Bar")));
+    ///             })
+    ///             .with_finalizer(move |world, owner| {
+    ///                 let bar = bar.unwrap().get().unwrap();
+    ///                 world.entity_mut(owner).insert(HealthBar(bar));
+    ///             }),
+    ///     )
+    ///     .id();
+    /// assert!(world.entity(owner).contains::<HealthBar>());
+    /// ```
+    pub fn spawn_capture(&mut self, spawnable: impl SpawnOnce) -> SpawnSlot {
+        let slot = SpawnSlot::default();
+        self.0.add_child(SpawnCapture(slot.clone(), spawnable));
+        slot
+    }
+
+    /// Spawns a child via a [`SpawnKey`] which may not be registered yet.
+    ///
+    /// Unlike [`SpawnChildBuilder::spawn_key`], this does not panic if the key is not registered when the child is spawned.
+    /// Instead, the child is spawned as an empty entity with a [`PendingSpawnKey`], and the spawn is retried
+    /// during each spawn system until the key is registered.
+    ///
+    /// This is useful to reference keys registered by other plugins, regardless of plugin order.
+    pub fn spawn_key_lazy(&mut self, key: impl Into<SpawnKey>) -> &mut Self {
+        self.0.add_child(LazyKey(key.into()));
+        self
+    }
+}
+
+/// A handle to the [`Entity`] of a child spawned with [`SpawnChildBuilder::spawn_capture`],
+/// or an entity spawned with [`SpawnCommands::spawn_key_unique`].
+///
+/// The entity is only available after it is spawned, typically from a [`SpawnFinalizer`].
+#[derive(Clone, Debug, Default)]
+pub struct SpawnSlot(Arc<OnceLock<Entity>>);
+
+impl SpawnSlot {
+    /// Returns the captured [`Entity`], or `None` if it has not been spawned yet.
+    pub fn get(&self) -> Option<Entity> {
+        self.0.get().copied()
+    }
+}
+
+/// A [`Component`] which marks an [`Entity`] waiting for its [`SpawnKey`] to be registered.
+///
+/// See [`SpawnChildBuilder::spawn_key_lazy`] for details.
+#[derive(Component, Clone, Debug, PartialEq, Eq)]
+pub struct PendingSpawnKey(pub SpawnKey);
+
+impl PendingSpawnKey {
+    /// Returns the [`SpawnKey`] this entity is waiting for.
+    pub fn key(&self) -> &SpawnKey {
+        &self.0
+    }
+}
+
+/// A [`Component`] which stores the name of a child spawned with [`SpawnChildBuilder::spawn_named`].
+#[derive(Component, Clone, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Component)]
+pub struct SpawnName(pub String);
+
+impl SpawnName {
+    /// Returns the name of this entity.
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Returns the first descendant of the given `root` [`Entity`] with the given [`SpawnName`], if any.
+///
+/// Descendants are searched in breadth-first order.
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Gun;
+///
+/// #[derive(Component)]
+/// struct Muzzle;
+///
+/// let mut world = World::default();
+/// let gun = world
+///     .spawn_once_with(Gun.with_children(|gun| {
+///         gun.spawn_named("muzzle", Muzzle);
+///     }))
+///     .id();
+/// let muzzle = find_child_by_spawn_name(&world, gun, "muzzle").unwrap();
+/// assert!(world.entity(muzzle).contains::<Muzzle>());
+/// ```
+pub fn find_child_by_spawn_name(world: &World, root: Entity, name: &str) -> Option<Entity> {
+    spawned_descendants(world, root).find(|&entity| {
+        world
+            .get::<SpawnName>(entity)
+            .is_some_and(|spawn_name| spawn_name.name() == name)
+    })
+}
+
+/// Returns an iterator over all descendants of the given `root` [`Entity`], excluding the root itself.
+///
+/// Descendants are visited in breadth-first order.
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Body;
+///
+/// #[derive(Component)]
+/// struct Head;
+///
+/// let mut world = World::default();
+/// let body = world
+///     .spawn_once_with(Body.with_children(|body| {
+///         body.spawn(Head.with_children(|head| {
+///             head.spawn(Name::new("Eye"));
+///         }));
+///     }))
+///     .id();
+/// assert_eq!(spawned_descendants(&world, body).count(), 2);
+/// ```
+pub fn spawned_descendants(world: &World, root: Entity) -> impl Iterator<Item = Entity> + '_ {
+    let mut entities = VecDeque::from([root]);
+    std::iter::from_fn(move || {
+        let entity = entities.pop_front()?;
+        if let Some(children) = world.get::<Children>(entity) {
+            entities.extend(children.iter().copied());
+        }
+        Some(entity)
+    })
+    .skip(1)
+}
+
+/// Returns an iterator over all descendants of the given `root` [`Entity`] which were spawned by a [`SpawnKey`],
+/// along with their keys.
+///
+/// Descendants are visited in breadth-first order. See [`SpawnSource`] for details.
+pub fn keyed_descendants(
+    world: &World,
+    root: Entity,
+) -> impl Iterator<Item = (Entity, &SpawnKey)> + '_ {
+    let spawnables = world.resource::<Spawnables>();
+    spawned_descendants(world, root).filter_map(|entity| {
+        let key = world.get::<SpawnSource>(entity)?.resolve(spawnables)?;
+        Some((entity, key))
+    })
+}
+
+/// A variant of [`Spawn`] which modifies its [`Entity`] directly, rather than by returning a [`Bundle`].
+///
+/// This is useful for spawnables whose components depend on runtime data, such as those which insert
+/// different sets of components conditionally. This trait is implemented for all types which implement [`Spawn`],
+/// and any type which implements it may be registered using [`AddSpawnable::add_spawnable`].
+///
+/// Any [`SpawnComposite`] bundles and spawn middleware are applied once the entity is modified.
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::prelude::*;
+///
+/// #[derive(Resource)]
+/// struct Night(bool);
+///
+/// #[derive(Component)]
+/// struct Owl;
+///
+/// #[derive(Component)]
+/// struct Chicken;
+///
+/// struct Bird;
+///
+/// impl SpawnDirect for Bird {
+///     fn spawn_direct(&self, entity: &mut EntityWorldMut) {
+///         if entity.world().resource::<Night>().0 {
+///             entity.insert(Owl);
+///         } else {
+///             entity.insert((Chicken, Name::new("Chicken")));
+///         }
+///     }
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, SpawnPlugin)).insert_resource(Night(true));
+/// app.add_spawnable("bird", Bird);
+///
+/// let bird = app.world_mut().spawn_key("bird").id();
+/// assert!(app.world().entity(bird).contains::<Owl>());
+/// ```
+pub trait SpawnDirect: 'static + Send + Sync {
+    /// Modifies the given [`Entity`] to spawn this spawnable into it.
+    fn spawn_direct(&self, entity: &mut EntityWorldMut);
+
+    /// Returns the resources which this spawnable requires to spawn (see [`Spawn::required_resources`]).
+    fn required_resources(&self) -> Vec<RequiredResource> {
+        Vec::new()
+    }
+
+    /// Returns the components which this spawnable inserts into its entity, if known (see [`Spawn::output_components`]).
+    ///
+    /// By default, this is `None`.
+    fn output_components(&self, _world: &mut World) -> Option<Vec<ComponentId>> {
+        None
+    }
+}
+
+impl<T: Spawn> SpawnDirect for T {
+    fn spawn_direct(&self, entity: &mut EntityWorldMut) {
+        let bundle = Spawn::spawn(self, entity.world(), entity.id());
+        entity.insert(bundle);
+    }
+
+    fn required_resources(&self) -> Vec<RequiredResource> {
+        Spawn::required_resources(self)
+    }
+
+    fn output_components(&self, world: &mut World) -> Option<Vec<ComponentId>> {
+        Spawn::output_components(self, world)
+    }
+}
+
+mod sealed {
+    use std::any::Any;
+
+    pub trait Sealed {
+        fn as_parametric(&self) -> Option<&dyn Any> {
+            None
+        }
+
+        /// Returns `true` if this spawnable spawns another [`SpawnKey`](super::SpawnKey) in its place,
+        /// which records itself instead of the key used to spawn this spawnable.
+        fn records_key(&self) -> bool {
+            false
+        }
+    }
+}
+
+pub(crate) use sealed::Sealed;
+
+/// A type-erased spawnable, as stored in [`Spawnables`].
+///
+/// This trait is sealed, and is implemented for all types which implement [`SpawnDirect`] (and therefore [`Spawn`]).
+pub trait Spawnable: Sealed + 'static + Send + Sync {
+    /// Spawns this spawnable into the given [`Entity`].
+    fn spawn(&self, world: &mut World, entity: Entity);
+
+    /// Returns the resources which this spawnable requires to spawn (see [`Spawn::required_resources`]).
+    fn required_resources(&self) -> Vec<RequiredResource> {
+        Vec::new()
+    }
+
+    /// Returns the components which this spawnable inserts into its entity, if known (see [`Spawn::output_components`]).
+    ///
+    /// By default, this is `None`.
+    fn output_components(&self, _world: &mut World) -> Option<Vec<ComponentId>> {
+        None
+    }
+}
+
+impl<T: SpawnDirect> Sealed for T {}
+
+impl<T: SpawnDirect> Spawnable for T {
+    fn spawn(&self, world: &mut World, entity: Entity) {
+        self.spawn_direct(&mut world.entity_mut(entity));
+        finish_spawned(world, entity);
+    }
+
+    fn required_resources(&self) -> Vec<RequiredResource> {
+        SpawnDirect::required_resources(self)
+    }
+
+    fn output_components(&self, world: &mut World) -> Option<Vec<ComponentId>> {
+        SpawnDirect::output_components(self, world)
+    }
+}
+
+struct SpawnableWithParam<T: SpawnWithParam> {
+    spawnable: T,
+    state: Mutex<Option<SystemState<T::Param>>>,
+}
+
+impl<T: SpawnWithParam> Sealed for SpawnableWithParam<T> {}
+
+impl<T: SpawnWithParam> Spawnable for SpawnableWithParam<T> {
+    fn spawn(&self, world: &mut World, entity: Entity) {
+        let mut state = self.state.lock().unwrap();
+        let state = state.get_or_insert_with(|| SystemState::new(world));
+        let bundle = self.spawnable.spawn_with_param(state.get(world), entity);
+        insert_spawned(world, entity, bundle);
+    }
+
+    fn output_components(&self, world: &mut World) -> Option<Vec<ComponentId>> {
+        Some(bundle_components::<T::Output>(world))
+    }
+}
+
+/// A [`Spawnable`] which inserts a list of reflected component values into its [`Entity`].
+///
+/// Each component is inserted using its [`ReflectComponent`] data from the [`AppTypeRegistry`].
+/// This allows spawnables to be defined entirely at runtime, such as from an editor or data files,
+/// using any component which is registered with the type registry and reflects [`Component`].
+///
+/// If a component type is not registered, or does not reflect [`Component`], an error is logged and it is skipped.
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::{prelude::*, ReflectSpawnable};
+///
+/// #[derive(Component, Reflect)]
+/// #[reflect(Component)]
+/// struct Health(u32);
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, SpawnPlugin)).register_type::<Health>();
+///
+/// let world = app.world_mut();
+/// let spawnable = ReflectSpawnable::new().with(Health(10)).with(Name::new("Chicken"));
+/// world.resource_mut::<Spawnables>().register_reflect("chicken", spawnable);
+///
+/// let chicken = world.spawn_key("chicken").id();
+/// assert_eq!(world.get::<Health>(chicken).unwrap().0, 10);
+/// ```
+#[derive(Default)]
+pub struct ReflectSpawnable(Vec<Box<dyn PartialReflect>>);
+
+impl ReflectSpawnable {
+    /// Creates an empty [`ReflectSpawnable`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a component value to be inserted when spawned.
+    pub fn with(mut self, component: impl PartialReflect) -> Self {
+        self.0.push(Box::new(component));
+        self
+    }
+
+    /// Adds a reflected component value to be inserted when spawned.
+    pub fn push(&mut self, component: Box<dyn PartialReflect>) {
+        self.0.push(component);
+    }
+
+    /// Returns the component values to be inserted when spawned.
+    pub fn components(&self) -> &[Box<dyn PartialReflect>] {
+        &self.0
+    }
+}
+
+impl From<Vec<Box<dyn PartialReflect>>> for ReflectSpawnable {
+    fn from(components: Vec<Box<dyn PartialReflect>>) -> Self {
+        Self(components)
+    }
+}
+
+impl SpawnDirect for ReflectSpawnable {
+    fn spawn_direct(&self, entity: &mut EntityWorldMut) {
+        let registry = entity.world().resource::<AppTypeRegistry>().clone();
+        let components = self
+            .0
+            .iter()
+            .map(|component| component.as_partial_reflect());
+        insert_reflect(entity, components, &registry.read());
+    }
+}
+
+/// Inserts the given reflected components into the given entity.
+///
+/// Any component which does not reflect [`Component`] is skipped, and an error is logged.
+pub(crate) fn insert_reflect<'a>(
+    entity: &mut EntityWorldMut,
+    components: impl IntoIterator<Item = &'a dyn PartialReflect>,
+    registry: &TypeRegistry,
+) {
+    for component in components {
+        let Some(reflect_component) = component
+            .get_represented_type_info()
+            .and_then(|info| registry.get(info.type_id()))
+            .and_then(|registration| registration.data::<ReflectComponent>())
+        else {
+            error!(
+                "component is not registered: {}",
+                component.reflect_type_path()
+            );
+            continue;
+        };
+        reflect_component.insert(entity, component, registry);
+    }
+}
+
+/// A [`Spawnable`] which spawns one of several [`SpawnKey`]s, chosen at random by weight.
+///
+/// Each time it is spawned, the sampler is called to get a random value in the range `[0, 1)`, which is
+/// used to choose a key. The chance of each key to be chosen is its weight relative to the sum of all weights.
+/// Keys with a weight of zero or less are never chosen.
+///
+/// The chosen key is spawned in place of the key of this spawnable, so each [`Entity`] records the chosen key as its
+/// [`SpawnSource`] and [`SpawnKind`]. If no key can be chosen, or the chosen key is not registered, an error is logged
+/// and a [`SpawnFailed`] event is sent. If the entity was spawned as a child, it is also despawned.
+///
+/// The sampler only has immutable access to the [`World`], so any random number generator stored in a [`Resource`]
+/// must use interior mutability.
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::{prelude::*, WeightedSpawnable};
+///
+/// #[derive(Component, Clone)]
+/// struct Grunt;
+///
+/// #[derive(Component, Clone)]
+/// struct Elite;
+///
+/// #[derive(Resource)]
+/// struct Roll(f32);
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, SpawnPlugin)).insert_resource(Roll(0.8));
+/// app.add_spawnable("grunt", Grunt);
+/// app.add_spawnable("elite", Elite);
+///
+/// let world = app.world_mut();
+/// let enemy = WeightedSpawnable::new(|world: &World| world.resource::<Roll>().0)
+///     .with("grunt", 0.7)
+///     .with("elite", 0.3);
+/// world.resource_mut::<Spawnables>().register("random_enemy", enemy);
+///
+/// let enemy = world.spawn_key("random_enemy").id();
+/// assert!(world.entity(enemy).contains::<Elite>());
+/// ```
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+pub struct WeightedSpawnable {
+    choices: Vec<(SpawnKey, f32)>,
+    sampler: Arc<dyn Fn(&World) -> f32 + Send + Sync>,
+}
+
+impl WeightedSpawnable {
+    /// Creates a new [`WeightedSpawnable`] with no keys, using the given sampler.
+    pub fn new(sampler: impl Fn(&World) -> f32 + Send + Sync + 'static) -> Self {
+        Self {
+            choices: Vec::new(),
+            sampler: Arc::new(sampler),
+        }
+    }
+
+    /// Adds the given [`SpawnKey`] with the given weight.
+    pub fn with(mut self, key: impl Into<SpawnKey>, weight: f32) -> Self {
+        self.push(key, weight);
+        self
+    }
+
+    /// Adds the given [`SpawnKey`] with the given weight.
+    pub fn push(&mut self, key: impl Into<SpawnKey>, weight: f32) -> &mut Self {
+        self.choices.push((key.into(), weight));
+        self
+    }
+
+    /// Returns all keys and their weights.
+    pub fn choices(&self) -> &[(SpawnKey, f32)] {
+        &self.choices
+    }
+
+    /// Returns the [`SpawnKey`] chosen by the given sample in the range `[0, 1)`, or `None` if no key can be chosen.
+    pub fn select(&self, sample: f32) -> Option<&SpawnKey> {
+        let weights = self.choices.iter().filter(|(_, weight)| *weight > 0.0);
+        let total: f32 = weights.clone().map(|(_, weight)| weight).sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let mut target = sample.clamp(0.0, 1.0) * total;
+        let mut last = None;
+        for (key, weight) in weights {
+            if target < *weight {
+                return Some(key);
+            }
+            target -= weight;
+            last = Some(key);
+        }
+        // Guards against rounding errors when the sample is close to 1:
+        last
+    }
+}
+
+impl Sealed for WeightedSpawnable {
+    fn records_key(&self) -> bool {
+        true
+    }
+}
+
+impl Spawnable for WeightedSpawnable {
+    fn spawn(&self, world: &mut World, entity: Entity) {
+        let sample = (self.sampler)(world);
+        let child = self
+            .select(sample)
+            .ok_or(SpawnError::NoWeightedKey)
+            .map(|key| KeyedChild::new(key.clone()))
+            .and_then(|child| {
+                let spawnable = child.fetch(world)?;
+                Ok(KeyedChild {
+                    spawnable: Some(spawnable),
+                    ..child
+                })
+            });
+        match child {
+            Ok(child) => child.spawn_once(world, entity),
+            Err(error) => {
+                error!("weighted spawnable failed to spawn: {error}");
+                // Children are expected to be despawned if cancelled (see `SpawnChildren::invoke`):
+                if SpawningChild::contains(world, entity) {
+                    world.entity_mut(entity).despawn_recursive();
+                }
+                world.send_event(SpawnFailed { entity, error });
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+struct ParametricSpawnable<P>(Box<dyn Fn(P, &mut World, Entity) + Send + Sync>);
+
+impl<P: 'static + Send + Sync> Sealed for ParametricSpawnable<P> {
+    fn as_parametric(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
+}
+
+impl<P: 'static + Send + Sync> Spawnable for ParametricSpawnable<P> {
+    fn spawn(&self, _: &mut World, _: Entity) {
+        panic!("spawnable requires parameters of type {}", type_name::<P>());
+    }
+}
+
+trait SpawnableOnce: 'static + Send + Sync {
+    fn spawn_once(self, world: &mut World, entity: Entity);
+
+    fn spawn_once_dyn(self: Box<Self>, world: &mut World, entity: Entity);
+
+    /// Returns the [`SpawnKey`] used to spawn this spawnable, if any.
+    fn key(&self) -> Option<&SpawnKey> {
+        None
+    }
+
+    /// Resolves any spawnable referenced by a [`SpawnKey`] using the given [`Spawnables`], so it is no longer looked up when spawned.
+    fn resolve(&mut self, _spawnables: &Spawnables) {}
+
+    /// Returns a [`SpawnNode`] which describes this spawnable without spawning it.
+    #[cfg(feature = "describe")]
+    fn describe(&self) -> SpawnNode {
+        SpawnNode {
+            key: self.key().cloned(),
+            children: Vec::new(),
+        }
+    }
+}
+
+impl<T: SpawnOnce> SpawnableOnce for T {
+    fn spawn_once(self, world: &mut World, entity: Entity) {
+        let bundle = self.spawn_once(world, entity);
+        insert_spawned(world, entity, bundle);
+    }
+
+    fn spawn_once_dyn(self: Box<Self>, world: &mut World, entity: Entity) {
+        SpawnableOnce::spawn_once(*self, world, entity);
+    }
+}
+
+impl SpawnKey {
+    fn spawned(self, world: &mut World, entity: Entity) {
+        SpawnKeyRef::Key(self).spawned(world, entity);
+    }
+}
+
+impl SpawnableOnce for SpawnKey {
+    fn spawn_once(self, world: &mut World, entity: Entity) {
+        KeyedChild::new(self).spawn_once(world, entity);
+    }
+
+    fn spawn_once_dyn(self: Box<Self>, world: &mut World, entity: Entity) {
+        SpawnableOnce::spawn_once(*self, world, entity);
+    }
+
+    fn key(&self) -> Option<&SpawnKey> {
+        Some(self)
+    }
+}
+
+/// A spawnable referenced by a [`SpawnKey`], which may be resolved before it is spawned.
+///
+/// When a [`SpawnChildren`] component is inserted, all of its keyed children are resolved (see [`SpawnableOnce::resolve`]).
+/// This means changes to [`Spawnables`] after that point do not affect children which are already queued.
+/// If a key is not registered yet, it is looked up again when the child is spawned.
+struct KeyedChild {
+    key: SpawnKey,
+    interned: Option<InternedSpawnKey>,
+    spawnable: Option<Arc<dyn Spawnable>>,
+}
+
+impl KeyedChild {
+    fn new(key: impl Into<SpawnKey>) -> Self {
+        Self {
+            key: key.into(),
+            interned: None,
+            spawnable: None,
+        }
+    }
+
+    fn fetch(&self, world: &World) -> Result<Arc<dyn Spawnable>, SpawnError> {
+        let spawnables = world.resource::<Spawnables>();
+        if let Some(spawnable) = self.spawnable.clone().or_else(|| spawnables.get(&self.key)) {
+            return Ok(spawnable);
+        }
+        let error = SpawnError::InvalidKey(self.key.clone());
+        let fallback = world
+            .get_resource::<SpawnConfig>()
+            .and_then(|config| config.missing_key_fallback.as_ref())
+            .and_then(|fallback| spawnables.get(fallback));
+        match fallback {
+            Some(fallback) => {
+                error!("{error}; spawning fallback instead");
+                Ok(fallback)
+            }
+            None => Err(error),
+        }
+    }
+}
+
+impl SpawnableOnce for KeyedChild {
+    fn spawn_once(self, world: &mut World, entity: Entity) {
+        #[cfg(feature = "trace")]
+        let _span = info_span!("spawn_key", key = %self.key.name()).entered();
+        let detect_key_cycles = world
+            .get_resource::<SpawnConfig>()
+            .is_some_and(|config| config.detect_key_cycles);
+        if detect_key_cycles {
+            if let Some(cycle) = find_key_cycle(world, entity, &self.key) {
+                let cycle: Vec<_> = cycle
+                    .iter()
+                    .map(|key| format!("{:?}", key.name()))
+                    .collect();
+                error!(
+                    "spawn key cycle detected at {entity:?}: {}",
+                    cycle.join(" -> ")
+                );
+                // Children are expected to be despawned if cancelled (see `SpawnChildren::invoke`):
+                if SpawningChild::contains(world, entity) {
+                    world.entity_mut(entity).despawn_recursive();
+                }
+                return;
+            }
+        }
+        let spawnable = self.fetch(world).unwrap_or_else(|error| panic!("{error}"));
+        spawnable.spawn(world, entity);
+        if spawnable.records_key() {
+            return;
+        }
+        match self.interned {
+            Some(interned) => SpawnKeyRef::Interned(interned).spawned(world, entity),
+            None => self.key.spawned(world, entity),
+        }
+    }
+
+    fn spawn_once_dyn(self: Box<Self>, world: &mut World, entity: Entity) {
+        SpawnableOnce::spawn_once(*self, world, entity);
+    }
+
+    fn key(&self) -> Option<&SpawnKey> {
+        Some(&self.key)
+    }
+
+    fn resolve(&mut self, spawnables: &Spawnables) {
+        if self.spawnable.is_none() {
+            self.spawnable = spawnables.get(&self.key);
+        }
+    }
+}
+
+/// Returns the chain of keys from the nearest ancestor of the given [`Entity`] spawned with the given key, if any.
+fn find_key_cycle(world: &World, entity: Entity, key: &SpawnKey) -> Option<Vec<SpawnKey>> {
+    let mut chain: Vec<_> = std::iter::successors(world.get::<Parent>(entity), |parent| {
+        world.get::<Parent>(parent.get())
+    })
+    .filter_map(|parent| world.get::<SpawnSource>(parent.get()))
+    .filter_map(|source| source.resolve(world.resource::<Spawnables>()).cloned())
+    .collect();
+    let index = chain.iter().position(|ancestor| ancestor == key)?;
+    chain.truncate(index + 1);
+    chain.reverse();
+    chain.push(key.clone());
+    Some(chain)
+}
+
+struct SpawnWith<T>(T);
+
+impl<T: Spawn> SpawnableOnce for SpawnWith<T> {
+    fn spawn_once(self, world: &mut World, entity: Entity) {
+        Spawnable::spawn(&self.0, world, entity);
+    }
+
+    fn spawn_once_dyn(self: Box<Self>, world: &mut World, entity: Entity) {
+        SpawnableOnce::spawn_once(*self, world, entity);
+    }
+}
+
+struct SpawnNamed<T>(SpawnName, T);
+
+impl<T: SpawnOnce> SpawnableOnce for SpawnNamed<T> {
+    fn spawn_once(self, world: &mut World, entity: Entity) {
+        SpawnableOnce::spawn_once(self.1, world, entity);
+        world.entity_mut(entity).insert(self.0);
+    }
+
+    fn spawn_once_dyn(self: Box<Self>, world: &mut World, entity: Entity) {
+        SpawnableOnce::spawn_once(*self, world, entity);
+    }
+}
+
+struct SpawnDetached<T>(T);
+
+impl<T: SpawnOnce> SpawnableOnce for SpawnDetached<T> {
+    fn spawn_once(self, world: &mut World, entity: Entity) {
+        world.entity_mut(entity).remove_parent();
+        SpawnableOnce::spawn_once(self.0, world, entity);
+    }
+
+    fn spawn_once_dyn(self: Box<Self>, world: &mut World, entity: Entity) {
+        SpawnableOnce::spawn_once(*self, world, entity);
+    }
+}
+
+struct TrySpawnable<T>(T);
+
+impl<T: TrySpawnOnce> SpawnableOnce for TrySpawnable<T> {
+    fn spawn_once(self, world: &mut World, entity: Entity) {
+        try_spawn_once(self.0, world, entity);
+    }
+
+    fn spawn_once_dyn(self: Box<Self>, world: &mut World, entity: Entity) {
+        self.spawn_once(world, entity);
+    }
+}
+
+struct SpawnCapture<T>(SpawnSlot, T);
+
+impl<T: SpawnOnce> SpawnableOnce for SpawnCapture<T> {
+    fn spawn_once(self, world: &mut World, entity: Entity) {
+        let Self(SpawnSlot(slot), spawnable) = self;
+        let _ = slot.set(entity);
+        SpawnableOnce::spawn_once(spawnable, world, entity);
+    }
+
+    fn spawn_once_dyn(self: Box<Self>, world: &mut World, entity: Entity) {
+        SpawnableOnce::spawn_once(*self, world, entity);
+    }
+}
+
+struct LazyKey(SpawnKey);
+
+impl SpawnableOnce for LazyKey {
+    fn spawn_once(self, world: &mut World, entity: Entity) {
+        if world.resource::<Spawnables>().contains(&self.0) {
+            self.0.spawn_once(world, entity);
+        } else {
+            world.entity_mut(entity).insert(PendingSpawnKey(self.0));
+        }
+    }
+
+    fn spawn_once_dyn(self: Box<Self>, world: &mut World, entity: Entity) {
+        SpawnableOnce::spawn_once(*self, world, entity);
+    }
+
+    #[cfg(feature = "describe")]
+    fn describe(&self) -> SpawnNode {
+        SpawnNode {
+            key: Some(self.0.clone()),
+            children: Vec::new(),
+        }
+    }
+}
+
+struct SpawnKeyWith<T>(KeyedChild, T);
+
+impl<T: Bundle> SpawnableOnce for SpawnKeyWith<T> {
+    fn spawn_once(self, world: &mut World, entity: Entity) {
+        // The bundle must be inserted after the spawnable so that it overrides its components:
+        self.0.spawn_once(world, entity);
+        world.entity_mut(entity).insert(self.1);
+    }
+
+    fn spawn_once_dyn(self: Box<Self>, world: &mut World, entity: Entity) {
+        SpawnableOnce::spawn_once(*self, world, entity);
+    }
+
+    fn key(&self) -> Option<&SpawnKey> {
+        self.0.key()
+    }
+
+    fn resolve(&mut self, spawnables: &Spawnables) {
+        self.0.resolve(spawnables);
+    }
+}
+
+struct SpawnKeyWithChildren(KeyedChild, SpawnChildren);
+
+impl SpawnableOnce for SpawnKeyWithChildren {
+    fn spawn_once(self, world: &mut World, entity: Entity) {
+        let Self(key, SpawnChildren(children)) = self;
+        key.spawn_once(world, entity);
+        let mut entity = world.entity_mut(entity);
+        if let Some(mut spawn_children) = entity.get_mut::<SpawnChildren>() {
+            spawn_children.0.extend(children);
+        } else {
+            entity.insert(SpawnChildren(children));
+        }
+    }
+
+    fn spawn_once_dyn(self: Box<Self>, world: &mut World, entity: Entity) {
+        SpawnableOnce::spawn_once(*self, world, entity);
+    }
+
+    fn key(&self) -> Option<&SpawnKey> {
+        self.0.key()
+    }
+
+    fn resolve(&mut self, spawnables: &Spawnables) {
+        self.0.resolve(spawnables);
+    }
+
+    #[cfg(feature = "describe")]
+    fn describe(&self) -> SpawnNode {
+        SpawnNode {
+            key: Some(self.0.key.clone()),
+            children: self.1.describe(),
+        }
+    }
+}
+
+struct SpawnKeyWithParams<P>(KeyedChild, P);
+
+impl<P: 'static + Send + Sync> SpawnableOnce for SpawnKeyWithParams<P> {
+    fn spawn_once(self, world: &mut World, entity: Entity) {
+        let spawnable = self
+            .0
+            .fetch(world)
+            .unwrap_or_else(|error| panic!("{error}"));
+        let Self(KeyedChild { key, .. }, params) = self;
+        let Some(ParametricSpawnable(spawn)) = spawnable
+            .as_parametric()
+            .and_then(|spawnable| spawnable.downcast_ref::<ParametricSpawnable<P>>())
+        else {
+            panic!("{}", SpawnError::InvalidParams(key, type_name::<P>()));
+        };
+        spawn(params, world, entity);
+        key.spawned(world, entity);
+    }
+
+    fn spawn_once_dyn(self: Box<Self>, world: &mut World, entity: Entity) {
+        SpawnableOnce::spawn_once(*self, world, entity);
+    }
+
+    fn key(&self) -> Option<&SpawnKey> {
+        self.0.key()
+    }
+
+    fn resolve(&mut self, spawnables: &Spawnables) {
+        self.0.resolve(spawnables);
+    }
+}
+
+/// Spawns an empty root [`Entity`] under the [`SpawnConfig::default_parent`], if any.
+///
+/// The entity is also marked with [`SpawnedEntity`] if enabled via [`SpawnConfig::mark_spawned_entities`].
+fn spawn_root(world: &mut World) -> Entity {
+    let entity = world.spawn_empty().id();
+    prepare_root(world, entity);
+    entity
+}
+
+/// Same as [`spawn_root`], but deferred.
+fn spawn_root_deferred(commands: &mut Commands) -> Entity {
+    let entity = commands.spawn_empty().id();
+    commands.queue(move |world: &mut World| prepare_root(world, entity));
+    entity
+}
+
+fn prepare_root(world: &mut World, entity: Entity) {
+    #[cfg(feature = "metrics")]
+    if let Some(mut metrics) = world.get_resource_mut::<SpawnMetrics>() {
+        metrics.spawns_this_frame += 1;
+    }
+    let Some(config) = world.get_resource::<SpawnConfig>() else {
+        return;
+    };
+    let (parent, mark) = (config.default_parent, config.mark_spawned_entities);
+    if mark {
+        world.entity_mut(entity).insert(SpawnedEntity);
+    }
+    let Some(parent) = parent else {
+        return;
+    };
+    if parent != entity && world.get_entity(parent).is_ok() {
+        world.entity_mut(parent).add_child(entity);
+    }
+}
+
+/// Inserts the output [`Bundle`] of a spawnable into the given [`Entity`], along with any [`SpawnComposite`] bundles.
+fn insert_spawned(world: &mut World, entity: Entity, bundle: impl Bundle) {
+    world.entity_mut(entity).insert(bundle);
+    finish_spawned(world, entity);
+}
+
+/// Inserts the outputs of a spawnable into all given entities in a single batch.
+///
+/// See [`insert_spawned`] for details.
+fn spawn_batch_into<T: Spawn>(world: &mut World, entities: &[Entity], spawnable: &T) {
+    let batch: Vec<_> = entities
+        .iter()
+        .map(|&entity| (entity, spawnable.spawn(world, entity)))
+        .collect();
+    world.insert_batch(batch);
+    for &entity in entities {
+        finish_spawned(world, entity);
+    }
+}
+
+/// Inserts any [`SpawnComposite`] bundles and runs all spawn middleware on an entity after its output is inserted.
+///
+/// This is called automatically for any [`Spawn`] or [`SpawnDirect`] (see [`AddSpawnable::add_spawn_middleware`]).
+pub(crate) fn finish_spawned(world: &mut World, entity: Entity) {
+    let mut entity_mut = world.entity_mut(entity);
+    if let Some(SpawnComposite(inserts)) = entity_mut.take::<SpawnComposite>() {
+        for insert in inserts {
+            insert(&mut entity_mut);
+        }
+    }
+    // Entities which are still loading are spawned again once loaded, so middleware only runs then:
+    #[cfg(feature = "asset")]
+    if entity_mut.contains::<asset::Loading>() {
+        return;
+    }
+    run_spawn_middleware(world, entity);
+}
+
+#[allow(clippy::type_complexity)]
+type SpawnMiddlewareFn = Arc<dyn Fn(&mut World, Entity) + Send + Sync>;
+
+/// A [`Resource`] which stores all functions registered with [`AddSpawnable::add_spawn_middleware`].
+///
+/// Functions are shared behind an [`Arc`] so they may be invoked without borrowing the world or cloning the list.
+#[derive(Resource, Default)]
+struct SpawnMiddleware(Arc<Vec<SpawnMiddlewareFn>>);
+
+impl SpawnMiddleware {
+    fn push(&mut self, f: SpawnMiddlewareFn) {
+        Arc::make_mut(&mut self.0).push(f);
+    }
+}
+
+/// Invokes all [`SpawnMiddleware`] functions, in order, for the given spawned [`Entity`].
+fn run_spawn_middleware(world: &mut World, entity: Entity) {
+    let Some(SpawnMiddleware(middleware)) = world.get_resource::<SpawnMiddleware>() else {
+        return;
+    };
+    if middleware.is_empty() {
+        return;
+    }
+    let middleware = middleware.clone();
+    for f in middleware.iter() {
+        if world.get_entity(entity).is_err() {
+            return;
+        }
+        f(world, entity);
+    }
+}
+
+fn try_spawn_once(spawnable: impl TrySpawnOnce, world: &mut World, entity: Entity) -> bool {
+    if let Some(bundle) = spawnable.try_spawn_once(world, entity) {
+        insert_spawned(world, entity, bundle);
+        true
+    } else {
+        world.entity_mut(entity).despawn_recursive();
+        false
+    }
+}
+
+fn on_insert_spawn_children(mut world: DeferredWorld, entity: Entity, id: ComponentId) {
+    resolve_child_keys(&mut world, entity);
+    validate_child_keys(world, entity, id);
+}
+
+/// Resolves all keyed children of the given entity so that later changes to [`Spawnables`] do not affect them.
+fn resolve_child_keys(world: &mut DeferredWorld, entity: Entity) {
+    if world.get_resource::<Spawnables>().is_none() {
+        return;
+    }
+    let Some(mut children) = world.get_mut::<SpawnChildren>(entity) else {
+        return;
+    };
+    let mut queue = std::mem::take(&mut children.0);
+    let spawnables = world.resource::<Spawnables>();
+    for child in &mut queue {
+        child.resolve(spawnables);
+    }
+    if let Some(mut children) = world.get_mut::<SpawnChildren>(entity) {
+        children.0 = queue;
+    }
+}
+
+fn validate_child_keys(world: DeferredWorld, entity: Entity, _: ComponentId) {
+    if !world
+        .get_resource::<SpawnConfig>()
+        .is_some_and(|config| config.validate_child_keys)
+    {
+        return;
+    }
+    let (Some(spawnables), Some(children)) = (
+        world.get_resource::<Spawnables>(),
+        world.get::<SpawnChildren>(entity),
+    ) else {
+        return;
+    };
+    for (index, child) in children.0.iter().enumerate() {
+        if let Some(key) = child.key().filter(|key| !spawnables.contains(key)) {
+            panic!("child #{index} of {entity:?} has an invalid spawn key: {key:?}");
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn should_spawn_children(
+    query: Query<(), Or<(With<SpawnChildren>, With<SpawnFinalizer>)>>,
+    queue: Option<Res<SpawnQueue>>,
+) -> bool {
+    !query.is_empty() || queue.is_some_and(|queue| !queue.is_empty())
+}
+
+/// A run condition which returns `true` if there are no pending [`SpawnChildren`], [`SpawnFinalizer`]s, or queued spawns.
+///
+/// This is useful to wait until a spawned hierarchy is complete, such as in tests or to hide a loading indicator.
+/// Note that spawns which are waiting for a [`SpawnKey`] to be registered are not considered.
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::{prelude::*, all_children_spawned};
+///
+/// fn on_settled() {
+///     // All children are spawned at this point.
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, SpawnPlugin))
+///     .add_systems(Update, on_settled.run_if(all_children_spawned));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn all_children_spawned(
+    query: Query<(), Or<(With<SpawnChildren>, With<SpawnFinalizer>)>>,
+    queue: Option<Res<SpawnQueue>>,
+) -> bool {
+    !should_spawn_children(query, queue)
+}
+
+/// Returns the number of entities with pending [`SpawnChildren`].
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::{prelude::*, pending_spawn_children_count};
+///
+/// #[derive(Component)]
+/// struct Chicken;
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, SpawnPlugin));
+/// app.world_mut().spawn(spawn_children(|parent| {
+///     parent.spawn(Chicken);
+/// }));
+/// assert_eq!(pending_spawn_children_count(app.world_mut()), 1);
+/// app.update();
+/// assert_eq!(pending_spawn_children_count(app.world_mut()), 0);
+/// ```
+pub fn pending_spawn_children_count(world: &mut World) -> usize {
+    world
+        .query_filtered::<(), With<SpawnChildren>>()
+        .iter(world)
+        .count()
+}
+
+fn spawn_pending_keys_system(
+    world: &mut World,
+    query: &mut QueryState<(Entity, &PendingSpawnKey)>,
+) {
+    let spawnables = world.resource::<Spawnables>();
+    let entities: Vec<_> = query
+        .iter(world)
+        .filter(|(_, pending)| spawnables.contains(pending.key()))
+        .map(|(entity, pending)| (entity, pending.0.clone()))
+        .collect();
+    for (entity, key) in entities {
+        world.entity_mut(entity).remove::<PendingSpawnKey>();
+        key.spawn_once(world, entity);
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn spawn_children_system(
+    world: &mut World,
+    query: &mut QueryState<Entity, Or<(With<SpawnChildren>, With<SpawnFinalizer>)>>,
+) {
+    invoke_spawn_children_of(world, |world, roots| roots.extend(query.iter(world)));
+}
+
+fn invoke_spawn_children(world: &mut World) {
+    invoke_spawn_children_with(world, |_, _| {});
+}
+
+/// Same as [`invoke_spawn_children`], but calls `f` with the parent of each spawned child.
+fn invoke_spawn_children_with(world: &mut World, f: impl FnMut(Entity, Entity)) {
+    invoke_spawn_children_of_with(
+        world,
+        |world, roots| {
+            // The query is removed from the world while in use, the same as `SpawnBuffer`:
+            let mut query = world
+                .remove_resource::<SpawnRootsQuery>()
+                .unwrap_or_else(|| SpawnRootsQuery(world.query_filtered()));
+            roots.extend(query.0.iter(world));
+            world.insert_resource(query);
+        },
+        f,
+    );
+}
+
+/// Query used by [`invoke_spawn_children`] to find entities with pending spawn children,
+/// kept between invocations so that its matched archetypes are only updated incrementally.
+#[allow(clippy::type_complexity)]
+#[derive(Resource)]
+struct SpawnRootsQuery(QueryState<Entity, Or<(With<SpawnChildren>, With<SpawnFinalizer>)>>);
+
+/// Buffers used by [`invoke_spawn_children_of`], kept between invocations to avoid reallocation.
+#[derive(Resource, Default)]
+struct SpawnBuffer {
+    spawned: Vec<Entity>,
+    batch: Vec<(Entity, usize)>,
+    next: Vec<(Entity, usize)>,
+}
+
+fn invoke_spawn_children_of(world: &mut World, roots: impl FnOnce(&mut World, &mut Vec<Entity>)) {
+    invoke_spawn_children_of_with(world, roots, |_, _| {});
+}
+
+fn invoke_spawn_children_of_with(
+    world: &mut World,
+    roots: impl FnOnce(&mut World, &mut Vec<Entity>),
+    mut f: impl FnMut(Entity, Entity),
+) {
+    #[cfg(feature = "trace")]
+    let _span = info_span!("invoke_spawn_children").entered();
+
+    let max_depth = world
+        .get_resource::<SpawnConfig>()
+        .and_then(|config| config.max_child_depth);
+
+    // The buffer is removed from the world while in use, so any nested invocations (i.e. from a finalizer) use their own:
+    let mut buffer = world.remove_resource::<SpawnBuffer>().unwrap_or_default();
+    let SpawnBuffer {
+        spawned,
+        batch,
+        next,
+    } = &mut buffer;
+
+    roots(world, spawned);
+    next.extend(spawned.iter().map(|&entity| (entity, 0)));
+    SpawnQueue::drain(world, |entity| {
+        next.push((entity, 0));
+        spawned.push(entity);
+    });
+
+    #[cfg(feature = "metrics")]
+    let mut child_count = 0;
+    #[cfg(feature = "metrics")]
+    let mut depth_reached = 0;
+    #[cfg(feature = "metrics")]
+    let mut iterations = 0;
+
+    while !next.is_empty() {
+        std::mem::swap(batch, next);
+        #[cfg(feature = "trace")]
+        let _span = debug_span!("spawn_children_batch", size = batch.len()).entered();
+        #[cfg(feature = "metrics")]
+        {
+            iterations += 1;
+            depth_reached = batch
+                .iter()
+                .fold(depth_reached, |max, &(_, depth)| max.max(depth));
+        }
+        for (entity, depth) in batch.drain(..) {
+            if let Some(max_depth) = max_depth.filter(|&max_depth| depth >= max_depth) {
+                error!("spawn children of {entity:?} exceed maximum depth ({max_depth})");
+                if let Ok(mut entity_mut) = world.get_entity_mut(entity) {
+                    entity_mut.remove::<SpawnChildren>();
+                }
+                continue;
+            }
+            SpawnChildren::invoke(world, entity, |child| {
+                next.push((child, depth + 1));
+                spawned.push(child);
+                #[cfg(feature = "metrics")]
+                {
+                    child_count += 1;
+                }
+                f(entity, child);
+            });
+        }
+        SpawnQueue::drain(world, |entity| {
+            next.push((entity, 0));
+            spawned.push(entity);
+        });
+    }
+
+    #[cfg(feature = "metrics")]
+    if let Some(mut metrics) = world.get_resource_mut::<SpawnMetrics>() {
+        // Roots are counted once each as they are prepared (see `prepare_root`):
+        metrics.spawns_this_frame += child_count;
+        metrics.max_depth_reached = metrics.max_depth_reached.max(depth_reached);
+        metrics.iterations += iterations;
+    }
+
+    // Finalize in reverse so that descendants are finalized before their ancestors:
+    for entity in spawned.drain(..).rev() {
+        SpawnFinalizer::invoke(world, entity);
+    }
+
+    world.insert_resource(buffer);
+}
+
+/// A [`Resource`] which collects spawn requests made while another spawnable is being spawned.
+///
+/// # Usage
+/// A spawnable only has immutable access to the [`World`], so it may not spawn other entities directly.
+/// Instead, it may queue them, which reserves their [`Entity`] immediately. Queued entities are spawned after the
+/// current spawnable (and any pending [`SpawnChildren`]) completes, during the same spawn pass. They are spawned as
+/// root entities, the same as [`SpawnWorld::spawn_key`] (see [`SpawnConfig::default_parent`]),
+/// so they may be parented manually, such as in a [`SpawnFinalizer`].
+///
+/// Queued spawns may queue more spawns. Each is spawned in the order it was queued.
+///
+/// This resource is added by the [`SpawnPlugin`].
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Nest(Entity);
+///
+/// #[derive(Component, Clone)]
+/// struct Egg;
+///
+/// struct Chicken;
+///
+/// impl Spawn for Chicken {
+///     type Output = Nest;
+///
+///     fn spawn(&self, world: &World, _: Entity) -> Nest {
+///         Nest(SpawnQueue::spawn_key(world, "egg"))
+///     }
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, SpawnPlugin));
+/// app.add_spawnable("egg", Egg);
+/// app.add_spawnable("chicken", Chicken);
+///
+/// let world = app.world_mut();
+/// let chicken = world.spawn_key("chicken").id();
+/// let egg = world.get::<Nest>(chicken).unwrap().0;
+/// assert!(world.entity(egg).contains::<Egg>());
+/// ```
+#[derive(Resource, Default)]
+pub struct SpawnQueue(Mutex<Vec<(Entity, Box<dyn SpawnableOnce>)>>);
+
+impl SpawnQueue {
+    /// Queues a spawnable to be spawned after the current spawn completes, and returns its reserved [`Entity`].
+    ///
+    /// # Warning
+    /// This function will panic if the [`World`] does not contain a [`SpawnQueue`].
+    pub fn spawn(world: &World, spawnable: impl SpawnOnce) -> Entity {
+        Self::push(world, spawnable)
+    }
+
+    /// Queues a spawnable via a [`SpawnKey`] to be spawned after the current spawn completes, and returns its reserved [`Entity`].
+    ///
+    /// # Warning
+    /// This function will panic if the [`World`] does not contain a [`SpawnQueue`].
+    pub fn spawn_key(world: &World, key: impl Into<SpawnKeyRef>) -> Entity {
+        Self::push(world, key.into())
+    }
+
+    /// Returns the number of queued spawns.
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+
+    /// Returns `true` if no spawns are queued.
+    pub fn is_empty(&self) -> bool {
+        self.0.lock().unwrap().is_empty()
+    }
+
+    fn push(world: &World, spawnable: impl SpawnableOnce) -> Entity {
+        let Some(queue) = world.get_resource::<SpawnQueue>() else {
+            panic!("spawn queue is not available; make sure `SpawnPlugin` is added");
+        };
+        let entity = world.entities().reserve_entity();
+        queue.0.lock().unwrap().push((entity, Box::new(spawnable)));
+        entity
+    }
+
+    /// Spawns all queued spawnables, including any queued while spawning, and calls `f` for each spawned [`Entity`].
+    fn drain(world: &mut World, mut f: impl FnMut(Entity)) {
+        loop {
+            let Some(queue) = world.get_resource::<SpawnQueue>() else {
+                return;
+            };
+            let queue = std::mem::take(&mut *queue.0.lock().unwrap());
+            if queue.is_empty() {
+                return;
+            }
+            // Queued entities are reserved, and only allocated once the world is flushed:
+            world.flush();
+            for (entity, spawnable) in queue {
+                if world.get_entity(entity).is_err() {
+                    continue;
+                }
+                prepare_root(world, entity);
+                spawnable.spawn_once_dyn(world, entity);
+                f(entity);
+            }
+        }
+    }
+}
+
+/// Spawns a single layer of pending [`SpawnChildren`] requests and returns the number of children spawned.
+///
+/// Unlike [`force_spawn_children`], this does not spawn any children of the newly spawned children.
+/// Instead, they are spawned by the next step. Once no [`SpawnChildren`] are left, the next step
+/// invokes all pending [`SpawnFinalizer`]s, descendants before ancestors, and returns `0`.
+///
+/// This is intended for debugging and tooling, such as visualizing how a spawned hierarchy is constructed.
+/// Note that [`SpawnConfig::max_child_depth`] is not enforced while stepping.
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use moonshine_spawn::{prelude::*, step_spawn_children};
+///
+/// #[derive(Component)]
+/// struct Chicken;
+///
+/// let mut world = World::new();
+/// world.spawn(Chicken.with_children(|chicken| {
+///     chicken.spawn(Chicken.with_children(|chicken| {
+///         chicken.spawn(Chicken);
+///         chicken.spawn(Chicken);
+///     }));
+/// }));
+/// assert_eq!(step_spawn_children(&mut world), 1);
+/// assert_eq!(step_spawn_children(&mut world), 2);
+/// assert_eq!(step_spawn_children(&mut world), 0);
+/// ```
+pub fn step_spawn_children(world: &mut World) -> usize {
+    let entities: Vec<_> = world
+        .query_filtered::<Entity, With<SpawnChildren>>()
+        .iter(world)
+        .collect();
+
+    if entities.is_empty() {
+        let mut entities: Vec<_> = world
+            .query_filtered::<Entity, With<SpawnFinalizer>>()
+            .iter(world)
+            .map(|entity| {
+                let depth = std::iter::successors(world.get::<Parent>(entity), |parent| {
+                    world.get::<Parent>(parent.get())
+                })
+                .count();
+                (entity, depth)
+            })
+            .collect();
+        entities.sort_by_key(|&(_, depth)| std::cmp::Reverse(depth));
+        for (entity, _) in entities {
+            SpawnFinalizer::invoke(world, entity);
+        }
+        return 0;
+    }
+
+    let mut count = 0;
+    for entity in entities {
+        SpawnChildren::invoke(world, entity, |_| count += 1);
+    }
+    count
+}
+
+/// Returns a [`SystemConfigs`] which immediately spawns all pending [`SpawnChildren`] requests.
+///
+/// # Usage
+/// Typically, the spawn system spawns children automatically during [`First`] schedule.
+/// In some cases, however, it may be necessary to forcibly spawn children due to ordering issues.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_spawn::{prelude::*, force_spawn_children};
+///
+/// #[derive(Component)]
+/// struct Bar;
+///
+/// #[derive(Bundle)]
+/// struct Foo {
+///     children: SpawnChildren,
+/// }
+///
+/// impl Foo {
+///     fn new() -> Self {
+///         Self {
+///             children: spawn_children(|parent| {
+///                 parent.spawn(Bar);
+///             })
+///         }
+///     }
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     commands.spawn(Foo::new());
+/// }
+///
+/// fn post_setup(bar: Query<&Bar>) {
+///     let _ = bar.single();
+///     // ...
+/// }
+///
+/// App::new()
+///     .add_plugins((MinimalPlugins, SpawnPlugin))
+///     // Without `force_spawn_children()`, `post_setup` would panic!
+///     .add_systems(Startup, (setup, force_spawn_children(), post_setup).chain())
+///     .update();
+/// ```
+pub fn force_spawn_children() -> SystemConfigs {
+    (
+        spawn_pending_keys_system.run_if(any_with_component::<PendingSpawnKey>),
+        spawn_children_system.run_if(should_spawn_children),
+    )
+        .chain()
+}
+
+/// Returns a [`SystemConfigs`] which immediately spawns pending [`SpawnChildren`] requests of the given entity.
+///
+/// Unlike [`force_spawn_children`], this only spawns children of the given entity and any descendants spawned
+/// from it, without checking the rest of the world. If the entity does not exist, this does nothing.
+pub fn force_spawn_children_for(entity: Entity) -> SystemConfigs {
+    (move |world: &mut World| {
+        if world.get_entity(entity).is_ok() {
+            invoke_spawn_children_of(world, |_, roots| roots.push(entity));
+        }
+    })
+    .into_configs()
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::RunSystemOnce, prelude::*};
+
+    use super::*;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, SpawnPlugin));
+        app
+    }
+
+    #[derive(Component, Clone)]
+    struct Foo;
+
+    #[derive(Component, Clone)]
+    struct Bar;
+
+    #[derive(Clone)]
+    struct FooWithBar;
+
+    impl Spawn for FooWithBar {
+        type Output = (Foo, SpawnChildren);
+
+        fn spawn(&self, _: &World, _: Entity) -> Self::Output {
+            Foo.with_children(|foo| {
+                foo.spawn(Bar);
+            })
+        }
+    }
+
+    struct FooWithFoo;
+
+    impl Spawn for FooWithFoo {
+        type Output = (Foo, SpawnChildren);
+
+        fn spawn(&self, _: &World, _: Entity) -> Self::Output {
+            Foo.with_children(|foo| {
+                foo.spawn_key("FOO");
+            })
+        }
+    }
+
+    #[test]
+    fn spawn_with_id() {
+        enum Prefab {
+            Foo,
+            Bar,
+        }
+
+        impl SpawnId for Prefab {
+            fn key_name(&self) -> &'static str {
+                match self {
+                    Prefab::Foo => "FOO",
+                    Prefab::Bar => "BAR",
+                }
+            }
+        }
+
+        let mut app = app();
+        app.add_spawnable(Prefab::Foo, Foo);
+        app.add_spawnable(Prefab::Bar, Bar);
+        let world = app.world_mut();
+        let foo = world.spawn_key(Prefab::Foo).id();
+        let bar = world.spawn_key("BAR").id();
+        assert!(world.entity(foo).contains::<Foo>());
+        assert!(world.entity(bar).contains::<Bar>());
+        assert_eq!(SpawnKey::from(Prefab::Bar), SpawnKey::new("BAR"));
+    }
+
+    #[test]
+    fn spawn_bundle() {
+        let mut app = app();
+        let world = app.world_mut();
+        let entity = world.spawn_once_with(Foo).id();
+        assert!(world.entity(entity).contains::<Foo>());
+    }
+
+    #[test]
+    fn spawn_bundle_deferred() {
+        let mut app = app();
+        let entity = {
+            let world = app.world_mut();
+            world
+                .run_system_once(|mut commands: Commands| commands.spawn_once_with(Foo).id())
+                .unwrap()
+        };
+        app.update();
+        let world = app.world();
+        assert!(world.entity(entity).contains::<Foo>());
+    }
+
+    #[test]
+    fn spawn_with_key() {
+        let mut app = app();
+        app.add_spawnable("FOO", Foo);
+        let world = app.world_mut();
+        let entity = world.spawn_key("FOO").id();
+        assert!(world.entity(entity).contains::<Foo>());
+    }
+
+    #[test]
+    fn spawn_with_key_deferred() {
+        let mut app = app();
+        app.add_spawnable("FOO", Foo);
+        let entity = {
+            let world = app.world_mut();
+            world
+                .run_system_once(|mut commands: Commands| commands.spawn_key("FOO").id())
+                .unwrap()
+        };
+        app.update();
+        let world = app.world();
+        assert!(world.entity(entity).contains::<Foo>());
+    }
+
+    #[test]
+    fn try_spawn_with_invalid_key() {
+        let mut app = app();
+        let world = app.world_mut();
+        let result = world.try_spawn_key("FOO").map(|entity| entity.id());
+        assert_eq!(result, Err(SpawnError::InvalidKey("FOO".into())));
+        assert_eq!(world.entities().len(), 0);
+    }
+
+    #[test]
+    fn try_spawn_with_parametric_key() {
+        let mut app = app();
+        app.add_parametric_spawnable("FOO", |_: u32, _: &World, _| Foo);
+        let world = app.world_mut();
+        let result = world.try_spawn_key("FOO").map(|entity| entity.id());
+        assert_eq!(result, Err(SpawnError::MissingParams("FOO".into())));
+        assert_eq!(world.entities().len(), 0);
+
+        let entity = world.commands().try_spawn_key("FOO").id();
+        world.flush();
+        assert!(world.get_entity(entity).is_err());
+    }
+
+    #[test]
+    fn try_spawn_with_invalid_key_deferred() {
+        let mut app = app();
+        let entity = {
+            let world = app.world_mut();
+            world
+                .run_system_once(|mut commands: Commands| commands.try_spawn_key("FOO").id())
+                .unwrap()
+        };
+        app.update();
+        let world = app.world();
+        assert!(world.get_entity(entity).is_err());
+        let events = world.resource::<Events<SpawnFailed>>();
+        let event = events.iter_current_update_events().next().unwrap();
+        assert_eq!(event.entity, entity);
+        assert_eq!(event.error, SpawnError::InvalidKey("FOO".into()));
+    }
+
+    #[test]
+    fn try_spawn_with_missing_key_fallback() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            SpawnPlugin.with_missing_key_fallback("FALLBACK"),
+        ));
+        app.add_spawnable("FALLBACK", Bar);
+        let world = app.world_mut();
+        let result = world.try_spawn_key("FOO").map(|entity| entity.id());
+        assert_eq!(result, Err(SpawnError::InvalidKey("FOO".into())));
+
+        let entity = world.commands().try_spawn_key("FOO").id();
+        world.flush();
+        assert!(world.get_entity(entity).is_err());
+        let events = world.resource::<Events<SpawnFailed>>();
+        let event = events.iter_current_update_events().next().unwrap();
+        assert_eq!(event.error, SpawnError::InvalidKey("FOO".into()));
+        assert!(world.query::<&Bar>().iter(world).next().is_none());
+    }
+
+    #[test]
+    fn try_spawn_interned_deferred() {
+        let mut app = app();
+        app.add_spawnable("FOO", Foo);
+        let world = app.world_mut();
+        let key = world.resource_mut::<Spawnables>().intern("FOO");
+        let entity = world.commands().try_spawn_key(key).id();
+        world.flush();
+        assert!(world.entity(entity).contains::<Foo>());
+        assert_eq!(world.get::<SpawnSource>(entity).unwrap().key(), &key.into());
+    }
+
+    #[test]
+    fn replace_spawnable() {
+        let mut app = app();
+        app.add_spawnable("FOO", Foo);
+        let world = app.world_mut();
+        let previous = world.resource_mut::<Spawnables>().replace("FOO", Bar);
+        assert!(previous.is_some());
+        let entity = world.spawn_key("FOO").id();
+        assert!(!world.entity(entity).contains::<Foo>());
+        assert!(world.entity(entity).contains::<Bar>());
+    }
+
+    #[test]
+    fn unregister_spawnable() {
+        let mut app = app();
+        let key = app.add_spawnable("FOO", Foo);
+        let world = app.world_mut();
+        let mut spawnables = world.resource_mut::<Spawnables>();
+        assert!(spawnables.unregister(&key).is_some());
+        assert!(spawnables.unregister(&key).is_none());
+        assert!(world.try_spawn_key(key).is_err());
+    }
+
+    #[test]
+    fn get_or_register_spawnable() {
+        let mut app = app();
+        let world = app.world_mut();
+        let mut spawnables = world.resource_mut::<Spawnables>();
+        let key = spawnables.get_or_register("FOO", Foo);
+        assert!(spawnables.contains(&key));
+        assert_eq!(spawnables.get_or_register("FOO", Bar), key);
+        let entity = world.spawn_key(key).id();
+        assert!(world.entity(entity).contains::<Foo>());
+        assert!(!world.entity(entity).contains::<Bar>());
+    }
+
+    #[test]
+    fn insert_spawn_key() {
+        let mut app = app();
+        app.add_spawnable("FOO", FooWithBar);
+        let world = app.world_mut();
+        let entity = world.spawn_empty().insert_spawn_key("FOO").id();
+        assert!(world.entity(entity).contains::<Foo>());
+        let children = world.entity(entity).get::<Children>().unwrap();
+        let child = children.iter().copied().next().unwrap();
+        assert!(world.entity(child).contains::<Bar>());
+    }
+
+    #[test]
+    fn insert_spawn_key_deferred() {
+        let mut app = app();
+        app.add_spawnable("FOO", FooWithBar);
+        let entity = app.world_mut().spawn_empty().id();
+        app.world_mut()
+            .run_system_once(move |mut commands: Commands| {
+                commands.entity(entity).insert_spawn_key("FOO");
+            })
+            .unwrap();
+        app.update();
+        let world = app.world();
+        assert!(world.entity(entity).contains::<Foo>());
+        let children = world.entity(entity).get::<Children>().unwrap();
+        let child = children.iter().copied().next().unwrap();
+        assert!(world.entity(child).contains::<Bar>());
+    }
+
+    #[test]
+    fn spawn_children_max_depth() {
+        let mut app = app();
+        app.world_mut()
+            .resource_mut::<SpawnConfig>()
+            .max_child_depth = Some(3);
+        app.add_spawnable("FOO", FooWithFoo);
+        let world = app.world_mut();
+        let mut entity = world.spawn_key("FOO").id();
+        let mut depth = 0;
+        while let Some(children) = world.entity(entity).get::<Children>() {
+            entity = children[0];
+            depth += 1;
+        }
+        assert_eq!(depth, 3);
+        assert!(!world.entity(entity).contains::<SpawnChildren>());
+    }
+
+    #[test]
+    fn spawned_by_key() {
+        #[derive(Resource, Default)]
+        struct Spawned(Vec<(Entity, SpawnKeyRef)>);
+
+        let mut app = app();
+        app.init_resource::<Spawned>();
+        app.add_spawnable("BAR", Bar);
+        app.add_observer(
+            |trigger: Trigger<SpawnedByKey>, mut spawned: ResMut<Spawned>| {
+                let SpawnedByKey { entity, key } = trigger.event().clone();
+                assert_eq!(trigger.entity(), entity);
+                spawned.0.push((entity, key));
+            },
+        );
+        let world = app.world_mut();
+        let entity = world.spawn_key("BAR").id();
+        let parent = world
+            .spawn_once_with(Foo.with_children(|foo| {
+                foo.spawn_key("BAR");
+            }))
+            .id();
+        let child = world.entity(parent).get::<Children>().unwrap()[0];
+        let spawned = &world.resource::<Spawned>().0;
+        assert_eq!(spawned, &[(entity, "BAR".into()), (child, "BAR".into())]);
+    }
+
+    #[test]
+    fn spawn_key_static() {
+        const FOO: SpawnKey = SpawnKey::from_static("FOO");
+        assert!(matches!(FOO.0, Cow::Borrowed("FOO")));
+        assert!(matches!(SpawnKey::new("FOO").0, Cow::Borrowed("FOO")));
+        assert_eq!(FOO, SpawnKey::from(String::from("FOO")));
+
+        let mut app = app();
+        app.add_spawnable(FOO, Foo);
+        let world = app.world_mut();
+        let entity = world.spawn_key("FOO").id();
+        assert!(world.entity(entity).contains::<Foo>());
+    }
+
+    #[test]
+    fn spawn_with_params() {
+        #[derive(Component)]
+        struct Baz(u32);
+
+        let mut app = app();
+        app.add_parametric_spawnable("BAZ", |value: u32, _: &World, _: Entity| Baz(value));
+        let world = app.world_mut();
+        let entity = world.spawn_key_with_params("BAZ", 42u32).id();
+        assert_eq!(world.get::<Baz>(entity).unwrap().0, 42);
+    }
+
+    #[test]
+    fn spawn_with_params_deferred() {
+        #[derive(Component)]
+        struct Baz(u32);
+
+        let mut app = app();
+        app.add_parametric_spawnable("BAZ", |value: u32, _: &World, _: Entity| Baz(value));
+        let entity = {
+            let world = app.world_mut();
+            world
+                .run_system_once(|mut commands: Commands| {
+                    commands.spawn_key_with_params("BAZ", 42u32).id()
+                })
+                .unwrap()
+        };
+        app.update();
+        let world = app.world();
+        assert_eq!(world.get::<Baz>(entity).unwrap().0, 42);
+    }
+
+    #[test]
+    #[should_panic]
+    fn spawn_with_invalid_params() {
+        let mut app = app();
+        app.add_parametric_spawnable("FOO", |_: u32, _: &World, _: Entity| Foo);
+        app.world_mut().spawn_key_with_params("FOO", "invalid");
+    }
+
+    #[test]
+    fn keys_sorted() {
+        let mut app = app();
+        for name in ["C", "A", "D", "B"] {
+            app.add_spawnable(name, Foo);
+        }
+        let spawnables = app.world().resource::<Spawnables>();
+        let names: Vec<_> = spawnables.keys_sorted().iter().map(|k| k.name()).collect();
+        assert_eq!(names, ["A", "B", "C", "D"]);
+    }
+
+    #[test]
+    fn spawn_batch() {
+        let mut app = app();
+        let world = app.world_mut();
+        let entities = world.spawn_batch_with(3, FooWithBar);
+        assert_eq!(entities.len(), 3);
+        for entity in entities {
+            assert!(world.entity(entity).contains::<Foo>());
+            assert_eq!(world.entity(entity).get::<Children>().unwrap().len(), 1);
+        }
+    }
+
+    #[test]
+    fn spawn_batch_deferred() {
+        let mut app = app();
+        let entities = app
+            .world_mut()
+            .run_system_once(|mut commands: Commands| commands.spawn_batch_with(3, Foo))
+            .unwrap();
+        app.update();
+        let world = app.world();
+        assert_eq!(entities.len(), 3);
+        for entity in entities {
+            assert!(world.entity(entity).contains::<Foo>());
+        }
+    }
+
+    struct MaybeFoo(bool);
+
+    impl TrySpawnOnce for MaybeFoo {
+        type Output = Foo;
+
+        fn try_spawn_once(self, _: &World, _: Entity) -> Option<Self::Output> {
+            self.0.then_some(Foo)
+        }
+    }
+
+    #[test]
+    fn try_spawn_once() {
+        let mut app = app();
+        let world = app.world_mut();
+        let entity = world.spawn_once_try_with(MaybeFoo(true)).unwrap().id();
+        assert!(world.entity(entity).contains::<Foo>());
+        assert!(world.spawn_once_try_with(MaybeFoo(false)).is_none());
+        assert_eq!(world.entities().len(), 1);
+    }
+
+    #[test]
+    fn try_spawn_once_deferred() {
+        let mut app = app();
+        let (a, b) = app
+            .world_mut()
+            .run_system_once(|mut commands: Commands| {
+                let a = commands.spawn_once_try_with(MaybeFoo(true)).id();
+                let b = commands.spawn_once_try_with(MaybeFoo(false)).id();
+                (a, b)
+            })
+            .unwrap();
+        app.update();
+        let world = app.world();
+        assert!(world.entity(a).contains::<Foo>());
+        assert!(world.get_entity(b).is_err());
+    }
+
+    #[test]
+    fn respawn() {
+        let mut app = app();
+        app.add_spawnable("FOO", FooWithBar);
+        let world = app.world_mut();
+        let entity = world.spawn_key("FOO").id();
+        assert_eq!(
+            world.get::<SpawnSource>(entity),
+            Some(&SpawnSource("FOO".into()))
+        );
+        let child = world.entity(entity).get::<Children>().unwrap()[0];
+
+        world.resource_mut::<Spawnables>().replace("FOO", Bar);
+        world.respawn(entity);
+        assert!(world.get_entity(child).is_err());
+        assert!(world.entity(entity).contains::<Foo>());
+        assert!(world.entity(entity).contains::<Bar>());
+        assert!(!world.entity(entity).contains::<Children>());
+    }
+
+    #[test]
+    fn spawn_source() {
+        let mut app = app();
+        app.add_spawnable("FOO", Foo);
+        app.add_spawnable("BAR", Bar);
+        let world = app.world_mut();
+        let a = world.spawn_key("FOO").id();
+        let b = world.spawn_key("FOO").id();
+        world.spawn_key("BAR");
+        world.spawn_once_with(Foo);
+        let mut instances: Vec<_> = world
+            .query::<(Entity, &SpawnSource)>()
+            .iter(world)
+            .filter_map(|(entity, source)| (source.key() == &"FOO".into()).then_some(entity))
+            .collect();
+        instances.sort();
+        assert_eq!(instances, [a, b]);
+        let registry = world.resource::<AppTypeRegistry>().read();
+        assert!(registry
+            .get(std::any::TypeId::of::<SpawnSource>())
+            .is_some());
+    }
+
+    #[test]
+    fn spawn_source_disabled() {
+        let mut app = app();
+        app.world_mut()
+            .resource_mut::<SpawnConfig>()
+            .record_spawn_source = false;
+        app.add_spawnable("FOO", Foo);
+        let world = app.world_mut();
+        let entity = world.spawn_key("FOO").id();
+        assert!(!world.entity(entity).contains::<SpawnSource>());
+    }
+
+    #[test]
+    fn spawnables_len() {
+        let mut app = app();
+        assert!(app.world().resource::<Spawnables>().is_empty());
+        app.add_spawnable("FOO", Foo);
+        app.add_spawnable("BAR", Bar);
+        app.add_spawnable("BAZ", FooWithBar);
+        let spawnables = app.world().resource::<Spawnables>();
+        assert_eq!(spawnables.len(), 3);
+        assert!(!spawnables.is_empty());
+    }
+
+    #[test]
+    fn spawn_plugin_run_if() {
+        #[derive(Resource)]
+        struct Enabled(bool);
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(Enabled(false))
+            .add_plugins(
+                SpawnPlugin
+                    .in_schedule(PreUpdate)
+                    .run_if(|enabled: Res<Enabled>| enabled.0),
+            );
+
+        let entity = app
+            .world_mut()
+            .spawn(Foo.with_children(|foo| {
+                foo.spawn(Bar);
+            }))
+            .id();
+        app.update();
+        assert!(app.world().get::<Children>(entity).is_none());
+
+        app.world_mut().resource_mut::<Enabled>().0 = true;
+        app.update();
+        assert_eq!(app.world().get::<Children>(entity).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn spawn_with_immediate() {
+        let mut app = app();
+        let world = app.world_mut();
+        let entity = world.commands().spawn_with_immediate(FooWithBar).id();
+        world.flush();
+        let children = world.get::<Children>(entity).unwrap();
+        assert!(world.entity(children[0]).contains::<Bar>());
+    }
+
+    #[test]
+    fn force_spawn_children_for() {
+        let mut app = app();
+        let world = app.world_mut();
+        let a = world.spawn(Foo.with_children(|foo| {
+            foo.spawn_with(FooWithBar);
+        }));
+        let a = a.id();
+        let b = world.spawn(Foo.with_children(|foo| {
+            foo.spawn(Bar);
+        }));
+        let b = b.id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(super::force_spawn_children_for(a));
+        schedule.run(world);
+        let child = world.get::<Children>(a).unwrap()[0];
+        assert_eq!(world.get::<Children>(child).unwrap().len(), 1);
+        assert!(world.get::<Children>(b).is_none());
+        assert!(world.entity(b).contains::<SpawnChildren>());
+    }
+
+    #[test]
+    fn spawn_children_len() {
+        let children = spawn_children(|foo| {
+            foo.spawn(Bar);
+            foo.spawn_key("FOO");
+        });
+        assert_eq!(children.len(), 2);
+        assert!(!children.is_empty());
+        assert!(spawn_children(|_| {}).is_empty());
+    }
+
+    #[test]
+    fn keyed_descendants() {
+        let mut app = app();
+        app.add_spawnable("FOO", Foo);
+        let world = app.world_mut();
+        let entity = world
+            .spawn_once_with(Foo.with_children(|foo| {
+                foo.spawn(Bar.with_children(|bar| {
+                    bar.spawn_key("FOO");
+                }));
+            }))
+            .id();
+        assert_eq!(spawned_descendants(world, entity).count(), 2);
+        let keyed: Vec<_> = super::keyed_descendants(world, entity).collect();
+        assert_eq!(keyed.len(), 1);
+        assert_eq!(keyed[0].1, &SpawnKey::new("FOO"));
+        assert!(world.entity(keyed[0].0).contains::<Foo>());
+    }
+
+    #[test]
+    fn derive_spawn() {
+        #[derive(moonshine_spawn_derive::Spawn)]
+        struct Prefab {
+            foo: Foo,
+            bar: Bar,
+            #[children]
+            children: Vec<FooWithBar>,
+        }
+
+        let mut app = app();
+        let world = app.world_mut();
+        let entity = world
+            .spawn_with(Prefab {
+                foo: Foo,
+                bar: Bar,
+                children: vec![FooWithBar, FooWithBar],
+            })
+            .id();
+        assert!(world.entity(entity).contains::<Foo>());
+        assert!(world.entity(entity).contains::<Bar>());
+        let children = world.get::<Children>(entity).unwrap();
+        assert_eq!(children.len(), 2);
+        assert_eq!(world.get::<Children>(children[0]).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn spawn_with_key_ci() {
+        let mut app = app();
+        app.add_spawnable(SpawnKey::new_ci("Foo"), Foo);
+        let world = app.world_mut();
+        let entity = world.spawn_key(SpawnKey::new_ci("FOO")).id();
+        assert!(world.entity(entity).contains::<Foo>());
+        let entity = world.spawn_key("foo").id();
+        assert!(world.entity(entity).contains::<Foo>());
+    }
+
+    #[test]
+    fn spawnables_keys_with_prefix() {
+        let mut spawnables = Spawnables::default();
+        spawnables.register("enemies/goblin", Foo);
+        spawnables.register("enemies/orc", Foo);
+        spawnables.register("items/sword", Foo);
+        let mut keys: Vec<_> = spawnables
+            .keys_with_prefix("enemies/")
+            .map(SpawnKey::name)
+            .collect();
+        keys.sort();
+        assert_eq!(keys, ["enemies/goblin", "enemies/orc"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "child #1")]
+    fn validate_child_keys() {
+        let mut app = app();
+        app.world_mut()
+            .resource_mut::<SpawnConfig>()
+            .validate_child_keys = true;
+        app.add_spawnable("FOO", Foo);
+        app.world_mut().spawn(Foo.with_children(|foo| {
+            foo.spawn_key("FOO");
+            foo.spawn_key("BAR");
+        }));
+    }
+
+    #[test]
+    fn spawn_with_fn() {
+        let mut app = app();
+        app.add_spawnable_with("FOO", |_, _| (Foo, Bar));
+        let world = app.world_mut();
+        let entity = world.spawn_key("FOO").id();
+        assert!(world.entity(entity).contains::<Foo>());
+        assert!(world.entity(entity).contains::<Bar>());
+    }
+
+    #[test]
+    fn spawn_with_param() {
+        #[derive(Resource, Component)]
+        struct Count(usize);
+
+        struct FooCount;
+
+        impl SpawnWithParam for FooCount {
+            type Param = (Res<'static, Count>, Query<'static, 'static, (), With<Foo>>);
+            type Output = Count;
+
+            fn spawn_with_param(
+                &self,
+                (count, query): SystemParamItem<Self::Param>,
+                _: Entity,
+            ) -> Self::Output {
+                Count(count.0 + query.iter().count())
+            }
+        }
+
+        let mut app = app();
+        app.insert_resource(Count(1))
+            .add_spawnable_with_param("FOO_COUNT", FooCount);
+        let world = app.world_mut();
+        world.spawn(Foo);
+        world.spawn(Foo);
+        let entity = world.spawn_key("FOO_COUNT").id();
+        assert_eq!(world.get::<Count>(entity).unwrap().0, 3);
+    }
+
+    #[test]
+    fn spawn_finalizer() {
+        #[derive(Component)]
+        struct Finalized(usize);
+
+        let mut app = app();
+        let entity = app
+            .world_mut()
+            .commands()
+            .spawn(
+                Foo.with_children(|foo| {
+                    foo.spawn(Bar.with_children(|bar| {
+                        bar.spawn(Foo);
+                    }));
+                })
+                .with_finalizer(|world, entity| {
+                    let count = spawned_descendants(world, entity).count();
+                    world.entity_mut(entity).insert(Finalized(count));
+                }),
+            )
+            .id();
+        app.world_mut().flush();
+        assert!(app.world().get::<Finalized>(entity).is_none());
+        app.update();
+        assert_eq!(app.world().get::<Finalized>(entity).unwrap().0, 2);
+    }
+
+    #[test]
+    fn spawn_children_order() {
+        #[derive(Component, PartialEq, Debug)]
+        struct Index(usize);
+
+        let mut app = app();
+        let world = app.world_mut();
+        let build = |children: &mut SpawnChildBuilder| {
+            for index in 0..3 {
+                children.spawn(Index(index));
+            }
+        };
+        let entity = world.spawn_once_with((Foo, spawn_children(build))).id();
+        let rev_entity = world.spawn_once_with((Foo, spawn_children_rev(build))).id();
+        let indices = |world: &World, entity: Entity| -> Vec<usize> {
+            world
+                .get::<Children>(entity)
+                .unwrap()
+                .iter()
+                .map(|&child| world.get::<Index>(child).unwrap().0)
+                .collect()
+        };
+        assert_eq!(indices(world, entity), [0, 1, 2]);
+        assert_eq!(indices(world, rev_entity), [2, 1, 0]);
+    }
+
+    #[test]
+    fn spawn_key_lazy() {
+        let mut app = app();
+        let entity = app
+            .world_mut()
+            .spawn_once_with(Foo.with_children(|foo| {
+                foo.spawn_key_lazy("BAR");
+            }))
+            .id();
+        let child = app.world().get::<Children>(entity).unwrap()[0];
+        assert!(app.world().entity(child).contains::<PendingSpawnKey>());
+
+        app.add_spawnable("BAR", FooWithBar);
+        app.update();
+        let world = app.world();
+        assert!(!world.entity(child).contains::<PendingSpawnKey>());
+        assert!(world.entity(child).contains::<Foo>());
+        assert_eq!(world.get::<Children>(child).unwrap().len(), 1);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn spawn_metrics() {
+        let mut app = app();
+        app.world_mut().spawn(Foo.with_children(|foo| {
+            foo.spawn(Bar.with_children(|bar| {
+                bar.spawn(Foo);
+                bar.spawn(Foo);
+            }));
+        }));
+        app.update();
+        let metrics = app.world().resource::<SpawnMetrics>();
+        assert_eq!(metrics.spawns_this_frame, 3);
+        assert_eq!(metrics.max_depth_reached, 2);
+        assert_eq!(metrics.iterations, 3);
+
+        app.update();
+        assert_eq!(app.world().resource::<SpawnMetrics>().spawns_this_frame, 0);
+
+        app.add_spawnable("FOO", FooWithBar);
+        app.world_mut().spawn_key("FOO");
+        assert_eq!(app.world().resource::<SpawnMetrics>().spawns_this_frame, 2);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn spawn_metrics_queued() {
+        struct Queue;
+
+        impl Spawn for Queue {
+            type Output = ();
+
+            fn spawn(&self, world: &World, _: Entity) {
+                SpawnQueue::spawn_key(world, "FOO");
+            }
+        }
+
+        let mut app = app();
+        app.add_spawnable("FOO", FooWithBar);
+        app.add_spawnable("QUEUE", Queue);
+
+        // Queued before the spawn system runs:
+        let world = app.world_mut();
+        SpawnQueue::spawn_key(world, "FOO");
+        app.update();
+        assert_eq!(app.world().resource::<SpawnMetrics>().spawns_this_frame, 2);
+
+        // Queued while spawning:
+        app.update();
+        app.world_mut().spawn_key("QUEUE");
+        assert_eq!(app.world().resource::<SpawnMetrics>().spawns_this_frame, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "[SpawnKey(\"FOO\"), SpawnKey(\"BAR\")]")]
+    fn register_many_collisions() {
+        let mut app = app();
+        app.add_spawnable("FOO", Foo);
+        app.add_spawnables([
+            ("FOO", Arc::new(Foo) as Arc<dyn Spawnable>),
+            ("BAR", Arc::new(Bar)),
+            ("BAR", Arc::new(Bar)),
+        ]);
+    }
+
+    #[test]
+    fn despawn_all_with_key() {
+        let mut app = app();
+        app.add_spawnable("FOO", Foo);
+        app.add_spawnable("BAR", Bar);
+        let world = app.world_mut();
+        let a = world.spawn_key("FOO").id();
+        let b = world.spawn_key("BAR").id();
+        world.entity_mut(b).with_child(Foo);
+        let c = world
+            .spawn_once_with(Bar.with_children(|bar| {
+                bar.spawn_key("FOO");
+            }))
+            .id();
+        let d = world.spawn_key("BAR").id();
+        let nested = world.spawn_key("FOO").id();
+        world.entity_mut(d).add_child(nested);
+
+        assert_eq!(world.despawn_all_with_key("FOO"), 1);
+        assert!(world.get_entity(a).is_err());
+        assert!(world.get_entity(b).is_ok());
+        // Keyed children belong to the hierarchy of their parent, even if it was spawned without a key:
+        let children = world.get::<Children>(c).unwrap();
+        assert!(world.entity(children[0]).contains::<Foo>());
+        assert!(world.get_entity(nested).is_ok());
+    }
+
+    #[test]
+    fn spawn_composite() {
+        #[derive(Component, PartialEq, Debug)]
+        struct Value(usize);
+
+        let mut app = app();
+        let world = app.world_mut();
+        let entity = world
+            .commands()
+            .spawn_once_with(
+                SpawnComposite::new()
+                    .with((Foo, Value(1)))
+                    .with_if(false, Bar)
+                    .with(Value(2)),
+            )
+            .id();
+        world.flush();
+        assert!(world.entity(entity).contains::<Foo>());
+        assert!(!world.entity(entity).contains::<Bar>());
+        assert!(!world.entity(entity).contains::<SpawnComposite>());
+        assert_eq!(world.get::<Value>(entity), Some(&Value(2)));
+    }
+
+    #[test]
+    fn spawn_key_with_children() {
+        let mut app = app();
+        app.add_spawnable("FOO_WITH_BAR", FooWithBar);
+        app.add_spawnable("FOO", Foo);
+        let world = app.world_mut();
+        let entity = world
+            .spawn_key_with_children("FOO_WITH_BAR", |foo| {
+                foo.spawn_key("FOO");
+            })
+            .id();
+        let children = world.get::<Children>(entity).unwrap();
+        assert_eq!(children.len(), 2);
+        assert!(world.entity(children[0]).contains::<Bar>());
+        assert!(world.entity(children[1]).contains::<Foo>());
+    }
+
+    #[test]
+    fn try_add_spawnable() {
+        let mut app = app();
+        assert_eq!(app.try_add_spawnable("FOO", Foo), Ok(SpawnKey::new("FOO")));
+        assert_eq!(
+            app.try_add_spawnable("FOO", Bar),
+            Err(SpawnRegisterError::DuplicateKey(SpawnKey::new("FOO")))
+        );
+        let world = app.world_mut();
+        let entity = world.spawn_key("FOO").id();
+        assert!(world.entity(entity).contains::<Foo>());
+    }
+
+    #[test]
+    fn spawn_capture() {
+        let mut app = app();
+        let world = app.world_mut();
+        let mut slot = None;
+        let entity = world
+            .commands()
+            .spawn_once_with(Foo.with_children(|foo| {
+                foo.spawn(Foo);
+                slot = Some(foo.spawn_capture(Bar));
+            }))
+            .id();
+        world.flush();
+        let slot = slot.unwrap();
+        assert_eq!(slot.get(), None);
+        app.update();
+        let world = app.world();
+        let child = world.get::<Children>(entity).unwrap()[1];
+        assert_eq!(slot.get(), Some(child));
+    }
+
+    #[test]
+    fn try_spawn_child() {
+        let mut app = app();
+        let world = app.world_mut();
+        let a = world
+            .spawn_once_with(Foo.with_children(|foo| {
+                foo.try_spawn(MaybeFoo(false));
+            }))
+            .id();
+        let b = world
+            .spawn_once_with(Foo.with_children(|foo| {
+                foo.try_spawn(MaybeFoo(false));
+                foo.try_spawn(MaybeFoo(true));
+            }))
+            .id();
+        assert!(world
+            .get::<Children>(a)
+            .is_none_or(|children| children.is_empty()));
+        let children = world.get::<Children>(b).unwrap();
+        assert_eq!(children.len(), 1);
+        assert!(world.entity(children[0]).contains::<Foo>());
+        assert_eq!(world.entities().len(), 3);
+    }
+
+    #[test]
+    #[cfg(feature = "kind")]
+    fn spawn_key_as() {
+        let mut app = app();
+        app.add_spawnable("FOO", (Foo, Bar));
+        let world = app.world_mut();
+        let foo: SpawnInstance<Foo> = world.spawn_key_as("FOO").unwrap();
+        assert!(world.entity(foo.entity()).contains::<Bar>());
+        let bar = world
+            .run_system_once(|mut commands: Commands| commands.spawn_key_as::<Bar>("FOO"))
+            .unwrap();
+        app.update();
+        assert!(app.world().entity(bar.entity()).contains::<Foo>());
+    }
+
+    #[test]
+    #[cfg(feature = "kind")]
+    fn spawn_key_as_invalid() {
+        let mut app = app();
+        app.add_spawnable("FOO", FooWithBar);
+        let world = app.world_mut();
+        let result = world.spawn_key_as::<Bar>("FOO").map(|bar| bar.entity());
+        assert_eq!(
+            result,
+            Err(SpawnError::MissingComponent(
+                "FOO".into(),
+                type_name::<Bar>()
+            ))
+        );
+        assert_eq!(world.entities().len(), 0);
+        assert_eq!(
+            world.spawn_key_as::<Bar>("BAR").map(|bar| bar.entity()),
+            Err(SpawnError::InvalidKey("BAR".into()))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "kind")]
+    fn spawn_key_as_invalid_deferred() {
+        let mut app = app();
+        app.add_spawnable("FOO", Foo);
+        let world = app.world_mut();
+        let bar = world.commands().spawn_key_as::<Bar>("FOO");
+        world.flush();
+        assert!(world.get_entity(bar.entity()).is_err());
+        let events = world.resource::<Events<SpawnFailed>>();
+        let event = events.iter_current_update_events().next().unwrap();
+        assert_eq!(event.entity, bar.entity());
+        assert_eq!(
+            event.error,
+            SpawnError::MissingComponent("FOO".into(), type_name::<Bar>())
+        );
+    }
+
+    #[test]
+    fn register_invalid_key() {
+        let mut spawnables = Spawnables::default();
+        assert_eq!(
+            spawnables.try_register(" ", Foo),
+            Err(SpawnRegisterError::InvalidKey(SpawnKey::new(" ")))
+        );
+        assert!(spawnables.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_or_register_invalid_key() {
+        let mut spawnables = Spawnables::default();
+        spawnables.get_or_register("", Foo);
+    }
+
+    #[test]
+    #[should_panic]
+    fn replace_invalid_key() {
+        let mut spawnables = Spawnables::default();
+        spawnables.replace(" ", Foo);
+    }
+
+    #[test]
+    fn spawn_into() {
+        let mut app = app();
+        app.add_spawnable(
+            "FOO",
+            SpawnFn(|_: &World, _| {
+                Foo.with_children(|foo| {
+                    foo.spawn_key("BAR");
+                })
+            }),
+        );
+        app.add_spawnable("BAR", Bar);
+        let mut other = World::new();
+        let mut spawnables = app.world_mut().resource_mut::<Spawnables>();
+        let entity = spawnables.spawn_into(&mut other, "FOO").unwrap();
+        assert!(other.entity(entity).contains::<Foo>());
+        let children = other.get::<Children>(entity).unwrap();
+        assert!(other.entity(children[0]).contains::<Bar>());
+        assert!(!other.contains_resource::<Spawnables>());
+        assert!(spawnables.spawn_into(&mut other, "BAZ").is_err());
+        assert!(spawnables.contains(&SpawnKey::new("FOO")));
+        assert!(spawnables.contains(&SpawnKey::new("BAR")));
+    }
+
+    #[test]
+    fn spawn_into_nested() {
+        let mut app = app();
+        app.add_spawnable("FOO", FooWithBar);
+        app.add_spawnable(
+            "BAR",
+            SpawnFn(|_: &World, _| {
+                Bar.with_children(|bar| {
+                    bar.spawn_key("FOO");
+                })
+            }),
+        );
+        app.add_spawnable(
+            "ROOT",
+            SpawnFn(|_: &World, _| {
+                spawn_children(|root| {
+                    root.spawn_key("BAR");
+                })
+            }),
+        );
+        app.add_parametric_spawnable("PARAMS", |_: u32, _: &World, _| {
+            Foo.with_children(|foo| {
+                foo.spawn_key("FOO");
+            })
+        });
+        let mut other = World::new();
+        let mut spawnables = app.world_mut().resource_mut::<Spawnables>();
+        let root = spawnables.spawn_into(&mut other, "ROOT").unwrap();
+        let bar = other.get::<Children>(root).unwrap()[0];
+        let foo = other.get::<Children>(bar).unwrap()[0];
+        assert!(other.entity(bar).contains::<Bar>());
+        assert!(other.entity(foo).contains::<Foo>());
+        assert_eq!(other.get::<Children>(foo).unwrap().len(), 1);
+
+        let entity = spawnables
+            .spawn_into_with_params(&mut other, "PARAMS", 1u32)
+            .unwrap();
+        assert!(other.get::<Children>(entity).is_some());
+        assert_eq!(
+            spawnables.spawn_into_with_params(&mut other, "PARAMS", 1.0f32),
+            Err(SpawnError::InvalidParams(
+                "PARAMS".into(),
+                type_name::<f32>()
+            ))
+        );
+        assert_eq!(spawnables.len(), 4);
+    }
+
+    #[test]
+    fn spawn_into_panic() {
+        let mut app = app();
+        app.add_spawnable("FOO", SpawnFn(|_: &World, _| -> Foo { panic!() }));
+        let mut other = World::new();
+        let mut spawnables = app.world_mut().resource_mut::<Spawnables>();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            spawnables.spawn_into(&mut other, "FOO").unwrap();
+        }));
+        assert!(result.is_err());
+        assert!(spawnables.contains(&SpawnKey::new("FOO")));
+    }
+
+    spawn_keys! {
+        mod keys {
+            FOO,
+            BAR = "bar",
+        }
+    }
+
+    #[test]
+    fn spawn_keys() {
+        let mut app = app();
+        app.add_spawnable(keys::FOO, Foo);
+        app.add_spawnable(keys::BAR, Bar);
+        let world = app.world_mut();
+        let entity = world.spawn_key(keys::FOO).id();
+        assert_eq!(
+            world.get::<SpawnSource>(entity).unwrap().key(),
+            &SpawnKey::new("FOO").into()
+        );
+        assert!(world
+            .resource::<Spawnables>()
+            .contains(&SpawnKey::new("bar")));
+    }
+
+    #[test]
+    fn spawn_key_with_override() {
+        #[derive(Component, Clone, PartialEq, Debug)]
+        struct Value(u32);
+
+        let mut app = app();
+        app.add_spawnable("FOO", (Foo, Value(1)));
+        let world = app.world_mut();
+        let a = world.spawn_key_with("FOO", Value(2)).id();
+        assert_eq!(world.get::<Value>(a), Some(&Value(2)));
+        let b = world
+            .run_system_once(|mut commands: Commands| commands.spawn_key_with("FOO", Value(3)).id())
+            .unwrap();
+        app.update();
+        assert_eq!(app.world().get::<Value>(b), Some(&Value(3)));
+    }
+
+    #[cfg(feature = "describe")]
+    #[test]
+    fn describe_children() {
+        let children = spawn_children(|foo| {
+            foo.spawn(Bar);
+            foo.spawn_key_lazy("BAR");
+            foo.spawn_key_with_children("FOO", |foo| {
+                foo.spawn_key("BAR");
+            });
+        });
+        let nodes = children.describe();
+        assert_eq!(nodes.len(), 3);
+        assert!(nodes[0].is_opaque());
+        assert_eq!(nodes[1].key, Some(SpawnKey::new("BAR")));
+        assert_eq!(nodes[2].key, Some(SpawnKey::new("FOO")));
+        assert_eq!(nodes[2].children[0].key, Some(SpawnKey::new("BAR")));
+    }
+
+    #[test]
+    fn with_computed() {
+        #[derive(Component)]
+        struct FooCount(usize);
+
+        let mut app = app();
+        let world = app.world_mut();
+        let entity = world
+            .commands()
+            .spawn_once_with(
+                Bar.with_children(|bar| {
+                    bar.spawn(Foo);
+                    bar.spawn(Bar.with_children(|bar| {
+                        bar.spawn(Foo);
+                    }));
+                })
+                .with_computed(|world, bar| {
+                    FooCount(
+                        spawned_descendants(world, bar)
+                            .filter(|&entity| world.entity(entity).contains::<Foo>())
+                            .count(),
+                    )
+                }),
+            )
+            .id();
+        world.flush();
+        assert!(!world.entity(entity).contains::<FooCount>());
+        app.update();
+        assert_eq!(app.world().get::<FooCount>(entity).unwrap().0, 2);
+    }
+
+    #[test]
+    fn step_spawn_children() {
+        let mut app = app();
+        let world = app.world_mut();
+        let entity = world
+            .spawn(
+                Foo.with_children(|foo| {
+                    foo.spawn(Bar.with_children(|bar| {
+                        bar.spawn(Foo);
+                    }));
+                    foo.spawn(Bar);
+                })
+                .with_finalizer(|world, entity| {
+                    world.entity_mut(entity).insert(Bar);
+                }),
+            )
+            .id();
+        assert_eq!(super::step_spawn_children(world), 2);
+        assert_eq!(spawned_descendants(world, entity).count(), 2);
+        assert_eq!(super::step_spawn_children(world), 1);
+        assert_eq!(spawned_descendants(world, entity).count(), 3);
+        assert!(!world.entity(entity).contains::<Bar>());
+        assert_eq!(super::step_spawn_children(world), 0);
+        assert!(world.entity(entity).contains::<Bar>());
+    }
+
+    #[test]
+    #[cfg(feature = "name")]
+    fn name_from_key() {
+        let mut app = app();
+        app.world_mut().resource_mut::<SpawnConfig>().name_from_key = true;
+        app.add_spawnable("FOO", Foo);
+        app.add_spawnable("BAR", (Bar, Name::new("Bar")));
+        let world = app.world_mut();
+        let foo = world.spawn_key("FOO").id();
+        let bar = world.spawn_key("BAR").id();
+        assert_eq!(world.get::<Name>(foo).unwrap().as_str(), "FOO");
+        assert_eq!(world.get::<Name>(bar).unwrap().as_str(), "Bar");
+    }
+
+    #[test]
+    fn spawnables_get() {
+        let mut app = app();
+        app.add_spawnable("FOO", Foo);
+        let world = app.world_mut();
+        let spawnable = world
+            .resource::<Spawnables>()
+            .get(&SpawnKey::new("FOO"))
+            .unwrap();
+        let entity = world.spawn_empty().id();
+        spawnable.spawn(world, entity);
+        assert!(world.entity(entity).contains::<Foo>());
+        assert!(world
+            .resource::<Spawnables>()
+            .get(&SpawnKey::new("BAR"))
+            .is_none());
+    }
+
+    #[test]
+    fn spawn_key_unique() {
+        let mut app = app();
+        app.add_spawnable("FOO", Foo);
+        let (a, b, c) = app
+            .world_mut()
+            .run_system_once(|mut commands: Commands| {
+                let a = commands.spawn_key_unique(1, "FOO").slot();
+                let b = commands.spawn_key_unique(1, "FOO").insert(Bar).slot();
+                let c = commands.spawn_key_unique(2, "FOO").slot();
+                (a, b, c)
+            })
+            .unwrap();
+        let world = app.world_mut();
+        let (a, b, c) = (a.get().unwrap(), b.get().unwrap(), c.get().unwrap());
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert!(world.entity(a).contains::<Foo>());
+        assert!(world.entity(a).contains::<Bar>());
+        assert!(world.entity(c).contains::<Foo>());
+        assert!(!world.entity(c).contains::<Bar>());
+        assert_eq!(world.query::<&Foo>().iter(world).count(), 2);
+        assert_eq!(world.spawn_key_unique(1, "FOO").id(), a);
+
+        world.despawn(a);
+        let d = world.spawn_key_unique(1, "FOO").id();
+        assert_ne!(d, a);
+        assert_eq!(world.resource::<SpawnIdentities<i32>>().get(&1), Some(d));
+    }
+
+    #[test]
+    fn extend_children() {
+        #[derive(Component, PartialEq, Debug)]
+        struct Index(usize);
+
+        let mut app = app();
+        let mut children = spawn_children(|foo| {
+            foo.spawn(Index(0));
+        });
+        children.push(Index(1));
+        children.extend(spawn_children(|foo| {
+            foo.spawn(Index(2));
+            foo.spawn(Index(3));
+        }));
+        let world = app.world_mut();
+        let entity = world.spawn_once_with((Foo, children)).id();
+        let children = world.get::<Children>(entity).unwrap();
+        for (index, &child) in children.iter().enumerate() {
+            assert_eq!(world.get::<Index>(child), Some(&Index(index)));
+        }
+        assert_eq!(children.len(), 4);
+    }
+
+    #[test]
+    fn spawn_set() {
+        #[derive(Resource)]
+        struct Spawned(bool);
+
+        let mut app = app();
+        app.insert_resource(Spawned(false)).add_systems(
+            First,
+            (|query: Query<(), With<Children>>, mut spawned: ResMut<Spawned>| {
+                spawned.0 = !query.is_empty();
+            })
+            .after(SpawnSet::SpawnChildren),
+        );
+        let world = app.world_mut();
+        world.commands().spawn_once_with(Foo.with_children(|foo| {
+            foo.spawn(Bar);
+        }));
+        world.flush();
+        app.update();
+        assert!(app.world().resource::<Spawned>().0);
+    }
+
+    #[test]
+    fn spawn_key_child() {
+        let mut app = app();
+        app.add_spawnable(
+            "FOO",
+            SpawnFn(|_: &World, _| {
+                Foo.with_children(|foo| {
+                    foo.spawn(Bar);
+                })
+            }),
+        );
+        let world = app.world_mut();
+        let a = world.spawn(Bar).with_spawn_key("FOO").id();
+        let (b, c) = world
+            .run_system_once(|mut commands: Commands| {
+                let b = commands.spawn(Bar).with_spawn_key("FOO").id();
+                let c = commands.spawn(Bar).id();
+                commands.spawn_key_child(c, "FOO");
+                (b, c)
+            })
+            .unwrap();
+        app.update();
+        let world = app.world();
+        for parent in [a, b, c] {
+            let children = world.get::<Children>(parent).unwrap();
+            assert_eq!(children.len(), 1);
+            assert!(world.entity(children[0]).contains::<Foo>());
+            assert_eq!(world.get::<Children>(children[0]).unwrap().len(), 1);
+        }
+    }
+
+    #[test]
+    fn detect_key_cycles() {
+        let mut app = app();
+        app.world_mut()
+            .resource_mut::<SpawnConfig>()
+            .detect_key_cycles = true;
+        app.add_spawnable(
+            "FOO",
+            SpawnFn(|_: &World, _| {
+                Foo.with_children(|foo| {
+                    foo.spawn_key("BAR");
+                })
+            }),
+        );
+        app.add_spawnable(
+            "BAR",
+            SpawnFn(|_: &World, _| {
+                Bar.with_children(|bar| {
+                    bar.spawn_key("FOO");
+                })
+            }),
+        );
+        let world = app.world_mut();
+        let foo = world.spawn_key("FOO").id();
+        let bar = world.get::<Children>(foo).unwrap()[0];
+        assert!(world.entity(bar).contains::<Bar>());
+        assert!(world
+            .get::<Children>(bar)
+            .is_none_or(|children| children.is_empty()));
+        assert_eq!(world.query::<&Foo>().iter(world).count(), 1);
+
+        let cycle = world.spawn_empty().id();
+        world.entity_mut(bar).add_child(cycle);
+        assert_eq!(
+            super::find_key_cycle(world, cycle, &SpawnKey::new("FOO")),
+            Some(vec![
+                SpawnKey::new("FOO"),
+                SpawnKey::new("BAR"),
+                SpawnKey::new("FOO")
+            ])
+        );
+    }
+
+    #[test]
+    fn spawn_key_from() {
+        let names = [String::from("FOO")];
+        let keys: Vec<SpawnKey> = names.iter().map(SpawnKey::from).collect();
+        assert_eq!(keys, [SpawnKey::new("FOO")]);
+        assert_eq!(SpawnKey::from(Cow::Borrowed("FOO")), SpawnKey::new("FOO"));
+        assert_eq!(
+            SpawnKey::from(Cow::<str>::Owned("FOO".to_owned())),
+            SpawnKey::new("FOO")
+        );
+    }
+
+    #[test]
+    fn spawn_key_at() {
+        let mut app = app();
+        app.add_spawnable("FOO", FooWithBar);
+        let world = app.world_mut();
+        let a = world.entities().reserve_entity();
+        world.spawn_key_at(a, "FOO");
+        assert!(world.entity(a).contains::<Foo>());
+        let child = world.get::<Children>(a).unwrap()[0];
+        assert!(world.entity(child).contains::<Bar>());
+
+        let b = world.entities().reserve_entity();
+        world
+            .run_system_once(move |mut commands: Commands| {
+                commands.spawn_key_at(b, "FOO");
+            })
+            .unwrap();
+        app.update();
+        let world = app.world();
+        assert!(world.entity(b).contains::<Foo>());
+        assert_eq!(world.get::<Children>(b).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn spawn_option() {
+        let mut app = app();
+        let world = app.world_mut();
+        let entity = world
+            .spawn_once_with(Foo.with_children(|foo| {
+                foo.spawn_option(Some(Bar));
+                foo.spawn_option(None::<Bar>);
+            }))
+            .id();
+        let children = world.get::<Children>(entity).unwrap();
+        assert_eq!(children.len(), 1);
+        assert!(world.entity(children[0]).contains::<Bar>());
+        assert_eq!(world.entities().len(), 2);
+    }
+
+    #[test]
+    fn spawn_detached() {
+        let mut app = app();
+        let world = app.world_mut();
+        let entity = world
+            .spawn_once_with(Foo.with_children(|foo| {
+                foo.spawn(Bar);
+                foo.spawn_detached(Bar.with_children(|bar| {
+                    bar.spawn(Foo);
+                }));
+            }))
+            .id();
+        assert_eq!(world.get::<Children>(entity).unwrap().len(), 1);
+        let detached = world
+            .query_filtered::<Entity, (With<Bar>, Without<Parent>)>()
+            .single(world);
+        assert_eq!(world.get::<Children>(detached).unwrap().len(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn spawn_key_serde() {
+        use bevy::reflect::serde::{ReflectDeserializer, ReflectSerializer};
+        use bevy::reflect::TypeRegistry;
+        use serde::de::DeserializeSeed;
+
+        let key = SpawnKey::new("enemies/goblin");
+        let text = ron::to_string(&key).unwrap();
+        assert_eq!(text, "\"enemies/goblin\"");
+        assert_eq!(ron::from_str::<SpawnKey>(&text).unwrap(), key);
+
+        let mut registry = TypeRegistry::default();
+        registry.register::<SpawnKey>();
+        let reflected = ron::to_string(&ReflectSerializer::new(&key, &registry)).unwrap();
+        assert!(reflected.contains(&text));
+        let mut deserializer = ron::Deserializer::from_str(&reflected).unwrap();
+        let value = ReflectDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(SpawnKey::from_reflect(&*value), Some(key));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn spawn_key_serde_case_insensitive() {
+        let mut app = app();
+        let key = app.add_spawnable(SpawnKey::new_ci("Goblin"), Foo);
+        let text = ron::to_string(&key).unwrap();
+        let key = ron::from_str::<SpawnKey>(&text).unwrap();
+        assert_eq!(key.name(), "Goblin");
+        let world = app.world_mut();
+        let entity = world.spawn_key(key).id();
+        assert!(world.entity(entity).contains::<Foo>());
+    }
+
+    #[test]
+    fn validate_required_resources() {
+        #[derive(Resource)]
+        struct Required;
+
+        struct NeedsRequired;
+
+        impl Spawn for NeedsRequired {
+            type Output = Foo;
+
+            fn spawn(&self, world: &World, _: Entity) -> Foo {
+                world.resource::<Required>();
+                Foo
+            }
+
+            fn required_resources(&self) -> Vec<RequiredResource> {
+                vec![RequiredResource::of::<Required>()]
+            }
+        }
+
+        let mut app = app();
+        app.add_spawnable("FOO", NeedsRequired);
+        app.add_spawnable("BAR", Bar);
+        let world = app.world_mut();
+        assert_eq!(
+            world.resource::<Spawnables>().validate(world),
+            Err(vec![SpawnError::MissingResource(
+                SpawnKey::new("FOO"),
+                type_name::<Required>()
+            )])
+        );
+        world.insert_resource(Required);
+        assert_eq!(world.resource::<Spawnables>().validate(world), Ok(()));
+    }
+
+    #[test]
+    fn spawn_key_child_survives_unregister() {
+        let mut app = app();
+        let key = app.add_spawnable("foo", Foo);
+        let world = app.world_mut();
+        let entity = world
+            .commands()
+            .spawn(spawn_children(|parent| {
+                parent.spawn_key(key.clone());
+            }))
+            .id();
+        world.flush();
+        world.resource_mut::<Spawnables>().unregister(&key);
+        app.update();
+        let world = app.world_mut();
+        let children = world.get::<Children>(entity).unwrap();
+        assert!(world.get::<Foo>(children[0]).is_some());
+    }
+
+    #[test]
+    fn spawn_children_reserve() {
+        let children = spawn_children(|parent| {
+            parent.reserve(64);
+            for _ in 0..8 {
+                parent.spawn(Foo);
+            }
+        });
+        assert_eq!(children.len(), 8);
+        assert!(children.0.capacity() >= 64);
+        assert!(SpawnChildren::with_capacity(16).0.capacity() >= 16);
+    }
+
+    #[test]
+    fn spawn_many_children() {
+        #[derive(Component)]
+        struct Tile(usize);
+
+        let mut app = app();
+        app.add_spawnable("foo", Foo);
+        let world = app.world_mut();
+        let entity = world
+            .spawn_once_with(spawn_children(|parent| {
+                parent
+                    .spawn_many((0..4).map(Tile))
+                    .spawn_keys(["foo", "foo"]);
+            }))
+            .id();
+        let children = world.get::<Children>(entity).unwrap();
+        assert_eq!(children.len(), 6);
+        assert_eq!(world.get::<Tile>(children[3]).unwrap().0, 3);
+        assert!(world.get::<Foo>(children[5]).is_some());
+    }
+
+    #[test]
+    fn spawn_interned() {
+        let mut app = app();
+        let world = app.world_mut();
+        let key = world.resource_mut::<Spawnables>().intern("foo");
+        assert_eq!(world.resource_mut::<Spawnables>().intern("foo"), key);
+        world.resource_mut::<Spawnables>().register("foo", Foo);
+        let entity = world.spawn_key(key).id();
+        assert!(world.get::<Foo>(entity).is_some());
+        let source = world.get::<SpawnSource>(entity).unwrap();
+        assert_eq!(source.key(), &SpawnKeyRef::Interned(key));
+        assert_eq!(
+            source.resolve(world.resource::<Spawnables>()),
+            Some(&"foo".into())
+        );
+        let parent = world
+            .spawn_once_with(Bar.with_children(|bar| {
+                bar.spawn_key(key);
+            }))
+            .id();
+        let child = world.get::<Children>(parent).unwrap()[0];
+        assert!(world.get::<Foo>(child).is_some());
+        assert_eq!(world.despawn_all_with_key("foo"), 1);
+        world.resource_mut::<Spawnables>().unregister(&"foo".into());
+        let spawnables = world.resource::<Spawnables>();
+        assert!(spawnables.get_interned(key).is_none());
+        assert_eq!(spawnables.interned_key(key), Some(&"foo".into()));
+    }
+
+    #[test]
+    fn spawn_interned_case_insensitive() {
+        let mut app = app();
+        app.add_spawnable(SpawnKey::new_ci("Foo"), Foo);
+        let world = app.world_mut();
+        let mut spawnables = world.resource_mut::<Spawnables>();
+        let key = spawnables.intern("Foo");
+        assert_eq!(spawnables.intern("foo"), key);
+        assert!(spawnables.get_interned(key).is_some());
+        spawnables.replace("FOO", Bar);
+        let entity = world.spawn_key(key).id();
+        assert!(world.entity(entity).contains::<Bar>());
+        world.spawn_key("fOO");
+        assert_eq!(world.despawn_all_with_key("foo"), 2);
+        let mut spawnables = world.resource_mut::<Spawnables>();
+        assert!(spawnables.unregister(&"FOO".into()).is_some());
+        assert!(spawnables.get_interned(key).is_none());
+    }
+
+    #[test]
+    fn spawn_key_eq_case_insensitive() {
+        let ci = SpawnKey::new_ci("Foo");
+        for plain in [SpawnKey::new("Foo"), SpawnKey::new("foo")] {
+            assert_ne!(ci, plain);
+            assert_ne!(plain, ci);
+        }
+        assert_eq!(ci, SpawnKey::new_ci("fOO"));
+        assert_eq!(SpawnKey::new_ci("fOO"), ci);
+    }
+
+    struct Direct;
+
+    impl SpawnDirect for Direct {
+        fn spawn_direct(&self, entity: &mut EntityWorldMut) {
+            entity.insert(Foo);
+        }
+    }
+
+    #[test]
+    fn spawn_direct() {
+        #[derive(Component)]
+        struct Layer;
+
+        let mut app = app();
+        app.add_spawnable("bar", Direct);
+        app.add_spawn_middleware(|world: &mut World, entity: Entity| {
+            world.entity_mut(entity).insert(Layer);
+        });
+        let world = app.world_mut();
+        let entity = world.spawn_key("bar").id();
+        assert!(world.get::<Foo>(entity).is_some());
+        assert!(world.get::<Layer>(entity).is_some());
+    }
+
+    #[test]
+    fn default_parent() {
+        let mut app = app();
+        app.add_spawnable("foo", Foo);
+        let world = app.world_mut();
+        let root = world.spawn_empty().id();
+        world.resource_mut::<SpawnConfig>().default_parent = Some(root);
+
+        let a = world.spawn_key("foo").id();
+        let b = world.commands().spawn_with(Foo).id();
+        world.flush();
+        let other = world.spawn_empty().id();
+        let c = world.spawn_key_child(other, "foo").id();
+        assert_eq!(world.get::<Parent>(a).unwrap().get(), root);
+        assert_eq!(world.get::<Parent>(b).unwrap().get(), root);
+        assert_eq!(world.get::<Parent>(c).unwrap().get(), other);
+
+        world.resource_mut::<SpawnConfig>().default_parent = None;
+        let d = world.spawn_key("foo").id();
+        assert!(world.get::<Parent>(d).is_none());
+    }
+
+    #[test]
+    fn spawn_template() {
+        let mut app = app();
+        let template = SpawnTemplate::new(|_: &World, _| (Foo, Bar));
+        let world = app.world_mut();
+        let a = world.spawn_with(template.clone()).id();
+        let b = world.commands().spawn_with(template.clone()).id();
+        world.flush();
+        let c = world
+            .spawn_once_with(Name::default().with_children(move |parent| {
+                parent.spawn_with(template);
+            }))
+            .id();
+        app.update();
+        let world = app.world_mut();
+        let children = world.get::<Children>(c).unwrap();
+        assert!(world.entity(children[0]).contains::<Bar>());
+        assert!(world.entity(a).contains::<Bar>());
+        assert!(world.entity(b).contains::<Bar>());
+        assert!(!world.entity(a).contains::<SpawnComposite>());
+    }
+
+    #[test]
+    fn spawn_children_despawned_by_observer() {
+        let mut app = app();
+        let world = app.world_mut();
+        world.add_observer(|trigger: Trigger<ChildSpawned>, mut commands: Commands| {
+            commands.entity(trigger.event().parent).despawn();
+        });
+        let entity = world.spawn_empty().id();
+        world.entity_mut(entity).insert(spawn_children(|parent| {
+            parent.spawn(Foo).spawn(Bar);
+        }));
+        app.update();
+        let world = app.world_mut();
+        assert!(world.get_entity(entity).is_err());
+        assert_eq!(world.query::<&Foo>().iter(world).count(), 1);
+        assert_eq!(world.query::<&Bar>().iter(world).count(), 0);
+        // No empty child is left behind for the skipped spawnable:
+        let mut empty = world.query_filtered::<(), (With<Parent>, Without<Foo>)>();
+        assert_eq!(empty.iter(world).count(), 0);
+    }
+
+    #[test]
+    fn mark_spawned_entities() {
+        let mut app = app();
+        app.add_spawnable("foo", Foo);
+        let world = app.world_mut();
+
+        let unmarked = world.spawn_key("foo").id();
+        assert!(!world.entity(unmarked).contains::<SpawnedEntity>());
+
+        world.resource_mut::<SpawnConfig>().mark_spawned_entities = true;
+        let a = world.spawn_key("foo").id();
+        let b = world.commands().spawn_with(Foo).id();
+        let plain = world.commands().spawn(Foo).id();
+        world.flush();
+        assert!(world.entity(a).contains::<SpawnedEntity>());
+        assert!(world.entity(b).contains::<SpawnedEntity>());
+        assert!(!world.entity(plain).contains::<SpawnedEntity>());
+    }
+
+    #[test]
+    fn spawn_queue() {
+        #[derive(Component)]
+        struct Link(Entity);
+
+        struct Bar;
+
+        impl Spawn for Bar {
+            type Output = Link;
+
+            fn spawn(&self, world: &World, _: Entity) -> Link {
+                Link(SpawnQueue::spawn_key(world, "foo"))
+            }
+        }
+
+        let mut app = app();
+        app.add_spawnable("foo", Foo);
+        app.add_spawnable("bar", Bar);
+        let world = app.world_mut();
+        let entity = world.commands().spawn_key("bar").id();
+        world.flush();
+        assert_eq!(world.resource::<SpawnQueue>().len(), 1);
+        app.update();
+        let world = app.world();
+        let Link(foo) = world.get::<Link>(entity).unwrap();
+        assert!(world.get::<Foo>(*foo).is_some());
+        assert!(world.resource::<SpawnQueue>().is_empty());
+    }
+
+    #[test]
+    fn components_of() {
+        let mut app = app();
+        app.add_spawnable("foo", Foo);
+        app.add_spawnable("bar", Direct);
+        let world = app.world_mut();
+        let foo = world.register_component::<Foo>();
+        assert_eq!(Spawnables::components_of(world, "foo"), Some(vec![foo]));
+        assert_eq!(Spawnables::components_of(world, "bar"), None);
+        assert_eq!(Spawnables::components_of(world, "baz"), None);
+    }
+
+    #[test]
+    fn spawn_key_collect() {
+        let mut app = app();
+        app.add_spawnable(
+            "foo",
+            SpawnFn(|_: &World, _| {
+                Foo.with_children(|parent| {
+                    parent.spawn(Foo.with_children(|parent| {
+                        parent.spawn(Foo);
+                    }));
+                    parent.spawn(Foo);
+                })
+            }),
+        );
+        let world = app.world_mut();
+        let (entity, children) = world.spawn_key_collect("foo");
+        assert_eq!(children.len(), 2);
+        assert_eq!(&children[..], &world.get::<Children>(entity).unwrap()[..]);
+    }
+
+    #[test]
+    fn missing_key_fallback() {
+        #[derive(Component, Clone)]
+        struct Missing;
+
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            SpawnPlugin.with_missing_key_fallback("missing"),
+        ));
+        app.add_spawnable("missing", Missing);
+        let world = app.world_mut();
+        let entity = world
+            .spawn_once_with(spawn_children(|parent| {
+                parent.spawn_key("foo");
+            }))
+            .id();
+        let children = world.get::<Children>(entity).unwrap();
+        assert!(world.get::<Missing>(children[0]).is_some());
+        assert_eq!(
+            world.get::<SpawnSource>(children[0]).unwrap().0,
+            "foo".into()
+        );
+    }
+
+    #[test]
+    fn spawn_kind() {
+        let mut app = app();
+        app.add_spawnable("foo", Foo);
+        app.add_spawnable("bar", Bar);
+        let world = app.world_mut();
+        let mut spawnables = world.resource_mut::<Spawnables>();
+        spawnables.set_kind("foo", "ui");
+        let kind = SpawnKind::new("ui");
+        let keys: Vec<_> = spawnables.keys_of_kind(&kind).cloned().collect();
+        assert_eq!(keys, vec![SpawnKey::new("foo")]);
+
+        let foo = world
+            .spawn_key_with_children("foo", |parent| {
+                parent.spawn_key("foo");
+            })
+            .id();
+        let bar = world.spawn_key("bar").id();
+        assert_eq!(world.get::<SpawnKind>(foo), Some(&kind));
+        assert!(world.get::<SpawnKind>(bar).is_none());
+
+        world.commands().despawn_all_of_kind("ui");
+        world.flush();
+        assert!(world.get_entity(foo).is_err());
+        assert!(world.get_entity(bar).is_ok());
     }
-}
 
-impl SpawnableOnce for SpawnKey {
-    fn spawn_once(self, world: &mut World, entity: Entity) {
-        if let Some(spawnable) = world.resource::<Spawnables>().fetch(&self) {
-            spawnable.spawn(world, entity);
-        } else {
-            panic!("invalid spawn key: {self:?}");
-        }
-    }
+    #[test]
+    fn child_spawned() {
+        #[derive(Resource, Default)]
+        struct Spawned(Vec<(Entity, Entity)>);
 
-    fn spawn_once_dyn(self: Box<Self>, world: &mut World, entity: Entity) {
-        SpawnableOnce::spawn_once(*self, world, entity);
+        let mut app = app();
+        app.init_resource::<Spawned>();
+        app.add_observer(
+            |trigger: Trigger<ChildSpawned>, mut spawned: ResMut<Spawned>| {
+                let ChildSpawned { parent, child } = *trigger.event();
+                spawned.0.push((parent, child));
+            },
+        );
+        let world = app.world_mut();
+        let entity = world
+            .commands()
+            .spawn(spawn_children(|parent| {
+                parent.spawn(Foo.with_children(|parent| {
+                    parent.spawn(Foo);
+                }));
+            }))
+            .id();
+        world.flush();
+        app.update();
+        let world = app.world();
+        let child = world.get::<Children>(entity).unwrap()[0];
+        let grandchild = world.get::<Children>(child).unwrap()[0];
+        assert_eq!(
+            world.resource::<Spawned>().0,
+            vec![(entity, child), (child, grandchild)]
+        );
     }
-}
 
-struct SpawnKeyWith<T>(SpawnKey, T);
-
-impl<T: Bundle> SpawnableOnce for SpawnKeyWith<T> {
-    fn spawn_once(self, world: &mut World, entity: Entity) {
-        self.0.spawn_once(world, entity);
-        world.entity_mut(entity).insert(self.1);
+    #[test]
+    fn register_alias() {
+        let mut app = app();
+        app.add_spawnable("foo", Foo);
+        let world = app.world_mut();
+        let mut spawnables = world.resource_mut::<Spawnables>();
+        spawnables.register_alias("bar", "foo").unwrap();
+        assert!(Arc::ptr_eq(
+            &spawnables.get(&"foo".into()).unwrap(),
+            &spawnables.get(&"bar".into()).unwrap()
+        ));
+        assert_eq!(
+            spawnables.register_alias("baz", "qux"),
+            Err(SpawnRegisterError::MissingKey("qux".into()))
+        );
+        assert_eq!(
+            spawnables.register_alias("bar", "foo"),
+            Err(SpawnRegisterError::DuplicateKey("bar".into()))
+        );
+        let entity = world.spawn_key("bar").id();
+        assert!(world.get::<Foo>(entity).is_some());
     }
 
-    fn spawn_once_dyn(self: Box<Self>, world: &mut World, entity: Entity) {
-        SpawnableOnce::spawn_once(*self, world, entity);
+    #[cfg(feature = "journal")]
+    #[test]
+    fn spawn_journal() {
+        let mut app = app();
+        app.add_spawnable("foo", Foo);
+        let world = app.world_mut();
+        let entity = world
+            .spawn_key_with_children("foo", |parent| {
+                parent.spawn(Bar).spawn_key("foo");
+            })
+            .id();
+        let children = world.get::<Children>(entity).unwrap().to_vec();
+        let records = world.resource_mut::<SpawnJournal>().drain();
+        assert_eq!(
+            records,
+            vec![
+                SpawnRecord {
+                    key: Some("foo".into()),
+                    parent: None,
+                    entity,
+                    child: false,
+                },
+                SpawnRecord {
+                    key: None,
+                    parent: Some(entity),
+                    entity: children[0],
+                    child: true,
+                },
+                SpawnRecord {
+                    key: Some("foo".into()),
+                    parent: Some(entity),
+                    entity: children[1],
+                    child: true,
+                },
+            ]
+        );
+        assert!(world.resource::<SpawnJournal>().is_empty());
     }
-}
 
-fn should_spawn_children(query: Query<(), With<SpawnChildren>>) -> bool {
-    !query.is_empty()
-}
+    #[cfg(feature = "journal")]
+    #[test]
+    fn spawn_journal_replay() {
+        fn app_with_spawnables() -> App {
+            let mut app = app();
+            app.add_spawnable("bar", Bar);
+            app.add_spawnable_with("foo", |_: &World, _| {
+                (
+                    Foo,
+                    spawn_children(|foo| {
+                        foo.spawn_key("bar").spawn(Bar);
+                    }),
+                )
+            });
+            app
+        }
 
-fn invoke_spawn_children(world: &mut World) {
-    let mut entities = Vec::new();
+        let mut app = app_with_spawnables();
+        let world = app.world_mut();
+        world.spawn_key("foo");
+        world.spawn_key("foo");
+        world.spawn_key("bar");
+        let records: Vec<_> = world.resource::<SpawnJournal>().roots().cloned().collect();
+        assert_eq!(records.len(), 3);
 
-    for entity in world.iter_entities() {
-        if entity.contains::<SpawnChildren>() {
-            entities.push(entity.id());
+        let mut other = app_with_spawnables();
+        let other_world = other.world_mut();
+        for record in &records {
+            let entity = other_world.spawn_empty().id();
+            other_world.spawn_key_at(entity, record.key.clone().unwrap());
         }
+
+        let count = |world: &mut World| {
+            (
+                world.query::<&Foo>().iter(world).count(),
+                world.query::<&Bar>().iter(world).count(),
+            )
+        };
+        assert_eq!(count(app.world_mut()), (2, 5));
+        assert_eq!(count(app.world_mut()), count(other.world_mut()));
     }
 
-    while !entities.is_empty() {
-        let batch = std::mem::take(&mut entities);
-        for entity in batch {
-            SpawnChildren::invoke(world, entity, |child| entities.push(child));
-        }
+    #[cfg(feature = "journal")]
+    #[test]
+    fn spawn_journal_limit() {
+        let mut app = app();
+        app.add_spawnable("foo", Foo);
+        let world = app.world_mut();
+        world.resource_mut::<SpawnJournal>().set_limit(Some(2));
+        let entities: Vec<_> = (0..3).map(|_| world.spawn_key("foo").id()).collect();
+        let journal = world.resource::<SpawnJournal>();
+        assert_eq!(journal.len(), 2);
+        let recorded: Vec<_> = journal.records().map(|record| record.entity).collect();
+        assert_eq!(recorded, entities[1..]);
     }
-}
 
-/// Returns a [`SystemConfigs`] which immediately spawns all pending [`SpawnChildren`] requests.
-///
-/// # Usage
-/// Typically, the spawn system spawns children automatically during [`First`] schedule.
-/// In some cases, however, it may be necessary to forcibly spawn children due to ordering issues.
-///
-/// # Example
-/// ```
-/// use bevy::prelude::*;
-/// use moonshine_spawn::{prelude::*, force_spawn_children};
-///
-/// #[derive(Component)]
-/// struct Bar;
-///
-/// #[derive(Bundle)]
-/// struct Foo {
-///     children: SpawnChildren,
-/// }
-///
-/// impl Foo {
-///     fn new() -> Self {
-///         Self {
-///             children: spawn_children(|parent| {
-///                 parent.spawn(Bar);
-///             })
-///         }
-///     }
-/// }
-///
-/// fn setup(mut commands: Commands) {
-///     commands.spawn(Foo::new());
-/// }
-///
-/// fn post_setup(bar: Query<&Bar>) {
-///     let _ = bar.single();
-///     // ...
-/// }
-///
-/// App::new()
-///     .add_plugins((MinimalPlugins, SpawnPlugin))
-///     // Without `force_spawn_children()`, `post_setup` would panic!
-///     .add_systems(Startup, (setup, force_spawn_children(), post_setup).chain())
-///     .update();
-/// ```
-pub fn force_spawn_children() -> SystemConfigs {
-    invoke_spawn_children.run_if(should_spawn_children)
-}
+    #[test]
+    fn add_spawn_children() {
+        let mut app = app();
+        let world = app.world_mut();
+        let entity = world
+            .commands()
+            .spawn(spawn_children(|parent| {
+                parent.spawn(Foo);
+            }))
+            .add_spawn_children(|parent| {
+                parent.spawn(Bar);
+            })
+            .id();
+        world.flush();
+        app.update();
+        let world = app.world();
+        let children = world.get::<Children>(entity).unwrap();
+        assert!(world.get::<Foo>(children[0]).is_some());
+        assert!(world.get::<Bar>(children[1]).is_some());
+    }
 
-#[cfg(test)]
-mod tests {
-    use bevy::{ecs::system::RunSystemOnce, prelude::*};
+    #[test]
+    fn spawn_key_from_path() {
+        let key = |path: &str| SpawnKey::from_path(Path::new(path)).name().to_owned();
+        assert_eq!(key("prefabs/enemies/goblin.ron"), "prefabs/enemies/goblin");
+        assert_eq!(
+            key("prefabs\\enemies\\goblin.ron"),
+            "prefabs/enemies/goblin"
+        );
+        assert_eq!(
+            key("prefabs\\enemies/goblin.spawn.ron"),
+            "prefabs/enemies/goblin"
+        );
+        assert_eq!(key("./prefabs//goblin"), "prefabs/goblin");
+        assert_eq!(key("prefabs/.goblin.ron"), "prefabs/.goblin");
+        assert_eq!(key("goblin"), "goblin");
+    }
 
-    use super::*;
+    #[test]
+    fn weighted_spawnable() {
+        let spawnable = WeightedSpawnable::new(|_: &World| 0.0)
+            .with("a", 0.7)
+            .with("none", 0.0)
+            .with("b", 0.3);
+        assert_eq!(spawnable.select(0.0), Some(&"a".into()));
+        assert_eq!(spawnable.select(0.69), Some(&"a".into()));
+        assert_eq!(spawnable.select(0.71), Some(&"b".into()));
+        assert_eq!(spawnable.select(1.0), Some(&"b".into()));
+        assert_eq!(WeightedSpawnable::new(|_: &World| 0.0).select(0.5), None);
 
-    fn app() -> App {
-        let mut app = App::new();
-        app.add_plugins((MinimalPlugins, SpawnPlugin));
-        app
-    }
+        #[derive(Resource)]
+        struct Roll(f32);
 
-    #[derive(Component, Clone)]
-    struct Foo;
+        let mut app = app();
+        app.add_spawnable("a", Foo);
+        app.add_spawnable("b", Bar);
+        let world = app.world_mut();
+        world.insert_resource(Roll(0.9));
+        world.resource_mut::<Spawnables>().register(
+            "random",
+            WeightedSpawnable::new(|world: &World| world.resource::<Roll>().0)
+                .with("a", 1.0)
+                .with("b", 1.0),
+        );
+        let entity = world.spawn_key("random").id();
+        assert!(world.entity(entity).contains::<Bar>());
+        assert_eq!(world.get::<SpawnSource>(entity).unwrap().key(), &"b".into());
+        world.resource_mut::<Roll>().0 = 0.1;
+        let entity = world.spawn_key("random").id();
+        assert!(world.entity(entity).contains::<Foo>());
+        assert_eq!(world.despawn_all_with_key("a"), 1);
 
-    #[derive(Component, Clone)]
-    struct Bar;
+        // Unregistered keys fail, like an empty selection:
+        app.add_spawnable(
+            "missing",
+            WeightedSpawnable::new(|_: &World| 0.0).with("unregistered", 1.0),
+        );
+        let world = app.world_mut();
+        let entity = world.spawn_key("missing").id();
+        assert!(!world.entity(entity).contains::<Foo>());
+        assert!(!world.entity(entity).contains::<Bar>());
+        let events = world.resource::<Events<SpawnFailed>>();
+        let event = events.iter_current_update_events().next().unwrap();
+        assert_eq!(event.entity, entity);
+        assert_eq!(event.error, SpawnError::InvalidKey("unregistered".into()));
+
+        // Children which fail are despawned:
+        let parent = world
+            .spawn_key_with_children("a", |a| {
+                a.spawn_key("missing");
+            })
+            .id();
+        assert!(world
+            .get::<Children>(parent)
+            .is_none_or(|children| children.is_empty()));
+    }
 
     #[test]
-    fn spawn_bundle() {
+    fn spawn_key_deferred() {
         let mut app = app();
+        app.add_spawnable(
+            "foo",
+            SpawnFn(|_: &World, _| {
+                (
+                    Foo,
+                    spawn_children(|foo| {
+                        foo.spawn(Foo);
+                    }),
+                )
+            }),
+        );
         let world = app.world_mut();
-        let entity = world.spawn_once_with(Foo).id();
-        assert!(world.entity(entity).contains::<Foo>());
+        let entity = world
+            .spawn_key_deferred("foo")
+            .add_spawn_children(|foo| {
+                foo.spawn(Bar);
+            })
+            .id();
+        assert!(world.get::<Children>(entity).is_none());
+
+        world.entity_mut(entity).spawn_pending_children();
+        let children = world.get::<Children>(entity).unwrap();
+        assert_eq!(children.len(), 2);
+        assert!(world.get::<Foo>(children[0]).is_some());
+        assert!(world.get::<Bar>(children[1]).is_some());
+        assert!(!world.entity(entity).contains::<SpawnChildren>());
     }
 
+    #[cfg(feature = "transform")]
     #[test]
-    fn spawn_bundle_deferred() {
+    fn spawn_key_at_transform() {
         let mut app = app();
-        let entity = {
-            let world = app.world_mut();
-            world
-                .run_system_once(|mut commands: Commands| commands.spawn_once_with(Foo).id())
-                .unwrap()
-        };
+        app.add_spawnable(
+            "foo",
+            SpawnFn(|_: &World, _| {
+                (Foo, Transform::default()).with_children(|parent| {
+                    parent.spawn(Transform::from_xyz(0.0, 1.0, 0.0));
+                })
+            }),
+        );
+        let transform = Transform::from_xyz(5.0, 0.0, 0.0);
+        let world = app.world_mut();
+        let entity = world
+            .commands()
+            .spawn_key_at_transform("foo", transform)
+            .id();
+        world.flush();
         app.update();
         let world = app.world();
-        assert!(world.entity(entity).contains::<Foo>());
+        assert_eq!(world.get::<Transform>(entity), Some(&transform));
+        let child = world.get::<Children>(entity).unwrap()[0];
+        assert_eq!(
+            world.get::<Transform>(child),
+            Some(&Transform::from_xyz(0.0, 1.0, 0.0))
+        );
     }
 
     #[test]
-    fn spawn_with_key() {
+    fn spawn_middleware() {
+        #[derive(Component, Debug, PartialEq)]
+        struct Order(Vec<u32>);
+
         let mut app = app();
-        app.add_spawnable("FOO", Foo);
+        app.add_spawn_middleware(|world: &mut World, entity: Entity| {
+            // Children are not spawned yet:
+            assert!(world.get::<Children>(entity).is_none());
+            world.entity_mut(entity).insert(Order(vec![1]));
+        })
+        .add_spawn_middleware(|world: &mut World, entity: Entity| {
+            world.get_mut::<Order>(entity).unwrap().0.push(2);
+        });
         let world = app.world_mut();
-        let entity = world.spawn_key("FOO").id();
-        assert!(world.entity(entity).contains::<Foo>());
+        let entity = world
+            .spawn_once_with(Foo.with_children(|parent| {
+                parent.spawn(Bar);
+            }))
+            .id();
+        let child = world.get::<Children>(entity).unwrap()[0];
+        assert_eq!(world.get::<Order>(entity), Some(&Order(vec![1, 2])));
+        assert_eq!(world.get::<Order>(child), Some(&Order(vec![1, 2])));
     }
 
     #[test]
-    fn spawn_with_key_deferred() {
+    fn reflect_spawnable() {
+        #[derive(Component, Reflect, Debug, PartialEq)]
+        #[reflect(Component)]
+        struct Health(u32);
+
+        // Not registered:
+        #[derive(Component, Reflect)]
+        #[reflect(Component)]
+        struct Hidden;
+
         let mut app = app();
-        app.add_spawnable("FOO", Foo);
-        let entity = {
-            let world = app.world_mut();
-            world
-                .run_system_once(|mut commands: Commands| commands.spawn_key("FOO").id())
-                .unwrap()
-        };
+        app.register_type::<Health>();
+        let world = app.world_mut();
+        let mut spawnable = ReflectSpawnable::new().with(Health(5));
+        spawnable.push(Box::new(Hidden));
+        world
+            .resource_mut::<Spawnables>()
+            .register_reflect("foo", spawnable);
+        let entity = world.spawn_key("foo").id();
+        assert_eq!(world.get::<Health>(entity), Some(&Health(5)));
+        assert!(world.get::<Hidden>(entity).is_none());
+    }
+
+    #[test]
+    fn all_children_spawned() {
+        let mut app = app();
+        let world = app.world_mut();
+        world.spawn(spawn_children(|parent| {
+            parent.spawn(Foo);
+        }));
+        assert_eq!(pending_spawn_children_count(world), 1);
+        assert!(!world.run_system_once(super::all_children_spawned).unwrap());
         app.update();
+        let world = app.world_mut();
+        assert_eq!(pending_spawn_children_count(world), 0);
+        assert!(world.run_system_once(super::all_children_spawned).unwrap());
+    }
+
+    #[test]
+    fn reflect_spawn_key() {
+        let mut app = app();
+        app.add_spawnable("foo", Foo);
         let world = app.world();
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let reflect_key = registry
+            .get_type_data::<ReflectSpawnKey>(TypeId::of::<SpawnKey>())
+            .unwrap();
+        assert_eq!(reflect_key.choices(world), [SpawnKey::new("foo")]);
+        assert!(reflect_key.is_registered(world, &SpawnKey::new("foo")));
+        assert!(!reflect_key.is_registered(world, &SpawnKey::new("bar")));
+        assert!(!reflect_key.is_registered(world, &Name::new("foo")));
+    }
+
+    #[test]
+    fn spawn_key_case_insensitive_lookup() {
+        let mut app = app();
+        app.add_spawnable(SpawnKey::new_ci("goblin"), Foo);
+        let world = app.world_mut();
+        let entity = world.spawn_key("Goblin").id();
+        assert!(world.entity(entity).contains::<Foo>());
+        let entity = world.commands().spawn_key("GOBLIN").id();
+        world.flush();
         assert!(world.entity(entity).contains::<Foo>());
+        assert!(!world.resource::<Spawnables>().contains(&"orc".into()));
+
+        let mut spawnables = world.resource_mut::<Spawnables>();
+        assert!(spawnables.unregister(&"GoBlIn".into()).is_some());
+        assert!(!spawnables.contains(&"Goblin".into()));
     }
 
     #[test]
@@ -582,6 +8078,173 @@ mod tests {
         assert!(world.entity(child).contains::<Bar>());
     }
 
+    #[test]
+    fn spawn_bundle_with_children_with_spawn() {
+        let mut app = app();
+        let world = app.world_mut();
+        let entity = world
+            .spawn_once_with(Foo.with_children(|foo| {
+                foo.spawn_with(FooWithBar);
+            }))
+            .id();
+        let child = world.entity(entity).get::<Children>().unwrap()[0];
+        assert!(world.entity(child).contains::<Foo>());
+        let grandchild = world.entity(child).get::<Children>().unwrap()[0];
+        assert!(world.entity(grandchild).contains::<Bar>());
+    }
+
+    #[test]
+    fn spawn_bundle_with_nested_children() {
+        #[derive(Component)]
+        struct Baz;
+
+        let mut app = app();
+        let world = app.world_mut();
+        let entity = world
+            .spawn_once_with(Foo.with_children(|foo| {
+                foo.spawn(Bar.with_children(|bar| {
+                    bar.spawn(Baz.with_children(|baz| {
+                        baz.spawn(Foo);
+                    }));
+                }));
+            }))
+            .id();
+        let bar = world.entity(entity).get::<Children>().unwrap()[0];
+        assert!(world.entity(bar).contains::<Bar>());
+        let baz = world.entity(bar).get::<Children>().unwrap()[0];
+        assert!(world.entity(baz).contains::<Baz>());
+        let foo = world.entity(baz).get::<Children>().unwrap()[0];
+        assert!(world.entity(foo).contains::<Foo>());
+        assert!(!world.entity(foo).contains::<Children>());
+    }
+
+    #[test]
+    fn spawn_bundle_with_nested_children_deferred() {
+        #[derive(Component)]
+        struct Baz;
+
+        let mut app = app();
+        let entity = app
+            .world_mut()
+            .run_system_once(|mut commands: Commands| {
+                commands
+                    .spawn_once_with(Foo.with_children(|foo| {
+                        foo.spawn(Bar.with_children(|bar| {
+                            bar.spawn(Baz);
+                        }));
+                    }))
+                    .id()
+            })
+            .unwrap();
+        app.update();
+        let world = app.world();
+        let bar = world.entity(entity).get::<Children>().unwrap()[0];
+        assert!(world.entity(bar).contains::<Bar>());
+        let baz = world.entity(bar).get::<Children>().unwrap()[0];
+        assert!(world.entity(baz).contains::<Baz>());
+    }
+
+    #[test]
+    fn spawn_bundle_with_children_if() {
+        let mut app = app();
+        app.add_spawnable("BAR", Bar);
+        let world = app.world_mut();
+        let entity = world
+            .spawn_once_with(Foo.with_children(|foo| {
+                foo.spawn_if(false, Bar)
+                    .spawn_key_if(false, "BAR")
+                    .spawn_if(true, Foo)
+                    .spawn_key_if(true, "BAR");
+            }))
+            .id();
+        let children = world.entity(entity).get::<Children>().unwrap();
+        assert_eq!(children.len(), 2);
+        assert!(world.entity(children[0]).contains::<Foo>());
+        assert!(world.entity(children[1]).contains::<Bar>());
+        assert_eq!(world.entities().len(), 3);
+    }
+
+    #[test]
+    fn find_child_by_spawn_name() {
+        let mut app = app();
+        let entity = app
+            .world_mut()
+            .run_system_once(|mut commands: Commands| {
+                commands
+                    .spawn_once_with(Foo.with_children(|foo| {
+                        foo.spawn(Bar.with_children(|bar| {
+                            bar.spawn_named("BAZ", Foo);
+                        }));
+                    }))
+                    .id()
+            })
+            .unwrap();
+        app.update();
+        let world = app.world();
+        let baz = super::find_child_by_spawn_name(world, entity, "BAZ").unwrap();
+        assert_eq!(world.get::<SpawnName>(baz).unwrap().name(), "BAZ");
+        assert!(world.entity(baz).contains::<Foo>());
+        assert!(super::find_child_by_spawn_name(world, entity, "QUX").is_none());
+    }
+
+    #[test]
+    fn spawn_children_with_parent() {
+        #[derive(Component)]
+        struct Owner(Entity);
+
+        struct Baz;
+
+        impl SpawnOnce for Baz {
+            type Output = Owner;
+
+            fn spawn_once(self, world: &World, entity: Entity) -> Self::Output {
+                Owner(world.get::<Parent>(entity).unwrap().get())
+            }
+        }
+
+        let mut app = app();
+        let world = app.world_mut();
+        let entity = world
+            .spawn_once_with(Foo.with_children(|foo| {
+                foo.spawn(Baz);
+            }))
+            .id();
+        let child = world.entity(entity).get::<Children>().unwrap()[0];
+        assert_eq!(world.get::<Owner>(child).unwrap().0, entity);
+    }
+
+    #[test]
+    fn spawn_with_context() {
+        #[derive(Component, PartialEq, Debug)]
+        struct Context(Option<Entity>, Entity);
+
+        struct Baz;
+
+        impl SpawnWithContext for Baz {
+            type Output = Context;
+
+            fn spawn_with_context(&self, context: &SpawnContext) -> Self::Output {
+                Context(context.parent(), context.root())
+            }
+        }
+
+        let mut app = app();
+        let world = app.world_mut();
+        let root = world
+            .spawn_once_with(Foo.with_children(|foo| {
+                foo.spawn(Bar.with_children(|bar| {
+                    bar.spawn_with(WithContext(Baz));
+                }));
+            }))
+            .id();
+        let bar = world.entity(root).get::<Children>().unwrap()[0];
+        let baz = world.entity(bar).get::<Children>().unwrap()[0];
+        assert_eq!(world.get::<Context>(baz), Some(&Context(Some(bar), root)));
+
+        let entity = world.spawn_with(WithContext(Baz)).id();
+        assert_eq!(world.get::<Context>(entity), Some(&Context(None, entity)));
+    }
+
     #[test]
     fn spawn_bundle_with_children_with_key() {
         let mut app = app();
@@ -0,0 +1,88 @@
+//! Support for spawning a [`DynamicScene`] using a [`SpawnKey`](crate::SpawnKey).
+//!
+//! This module is only available with the `scene` feature.
+
+use bevy_asset::Handle;
+use bevy_ecs::prelude::*;
+use bevy_scene::{DynamicScene, DynamicSceneRoot};
+
+use crate::Spawn;
+
+/// A spawnable which instantiates a [`DynamicScene`] as children of the spawned entity.
+///
+/// The scene is spawned using the `SceneSpawner`, which requires the `ScenePlugin`.
+/// Any top-level entities of the scene are parented to the spawned entity.
+///
+/// If the scene is not loaded yet when this spawnable is spawned, its instantiation is deferred until it is loaded.
+///
+/// # Example
+/// ```
+/// use bevy::prelude::*;
+/// use moonshine_spawn::{prelude::*, scene::SceneSpawnable};
+///
+/// fn register_level(mut spawnables: ResMut<Spawnables>, server: Res<AssetServer>) {
+///     spawnables.register("Level1", SceneSpawnable(server.load("levels/level1.scn.ron")));
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct SceneSpawnable(pub Handle<DynamicScene>);
+
+impl SceneSpawnable {
+    /// Returns the handle to the [`DynamicScene`] of this spawnable.
+    pub fn handle(&self) -> &Handle<DynamicScene> {
+        &self.0
+    }
+}
+
+impl Spawn for SceneSpawnable {
+    type Output = DynamicSceneRoot;
+
+    fn spawn(&self, _world: &World, _entity: Entity) -> Self::Output {
+        DynamicSceneRoot(self.0.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::scene::ScenePlugin;
+
+    use super::*;
+    use crate::prelude::*;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Foo;
+
+    #[test]
+    fn spawn_scene() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            ScenePlugin,
+            SpawnPlugin,
+        ))
+        .register_type::<Foo>();
+
+        let scene = {
+            let mut scene_world = World::new();
+            scene_world.insert_resource(app.world().resource::<AppTypeRegistry>().clone());
+            scene_world.spawn(Foo);
+            DynamicScene::from_world(&scene_world)
+        };
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<DynamicScene>>()
+            .add(scene);
+        app.add_spawnable("LEVEL", SceneSpawnable(handle));
+
+        let entity = app.world_mut().spawn_key("LEVEL").id();
+        app.update();
+
+        let world = app.world();
+        let children = world.entity(entity).get::<Children>().unwrap();
+        assert_eq!(children.len(), 1);
+        assert!(world.entity(children[0]).contains::<Foo>());
+    }
+}